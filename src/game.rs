@@ -1,8 +1,26 @@
+mod achievements;
+mod arena;
+mod arena_generator;
+mod attributes;
+mod balance;
 mod battle;
+mod class;
+mod collision;
+mod crafting;
+mod difficulty;
+mod enemy;
 mod equipment;
+mod hall_of_fame;
+mod inventory;
 mod item;
+mod item_sets;
+mod lifetime_stats;
 mod message_queue;
+mod numeric;
+mod physics;
 pub mod player;
 pub mod scenes;
+mod shop;
+mod skill_tree;
 pub mod ui;
 mod utils;
@@ -0,0 +1,135 @@
+use super::shop::shop_items;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Something that happened to the player which achievements may care about.
+pub enum GameEvent<'a> {
+    BattleWon,
+    LevelReached(u128),
+    CoinsChanged(u128),
+    ItemBought(&'a str),
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AchievementId {
+    FirstWin,
+    Level10,
+    Coins1000,
+    BuyEverything,
+}
+
+pub struct Achievement {
+    pub id: AchievementId,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 4] = [
+    Achievement {
+        id: AchievementId::FirstWin,
+        name: "First blood",
+        description: "Win your first battle",
+    },
+    Achievement {
+        id: AchievementId::Level10,
+        name: "Seasoned",
+        description: "Reach level 10",
+    },
+    Achievement {
+        id: AchievementId::Coins1000,
+        name: "Treasure hoard",
+        description: "Hold 1000 coins at once",
+    },
+    Achievement {
+        id: AchievementId::BuyEverything,
+        name: "Big spender",
+        description: "Buy every item in the shop",
+    },
+];
+
+/// Per character progress towards every achievement.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct Achievements {
+    #[serde(default)]
+    wins: u128,
+    #[serde(default)]
+    best_level: u128,
+    #[serde(default)]
+    most_coins: u128,
+    #[serde(default)]
+    bought_items: BTreeSet<String>,
+    #[serde(default)]
+    unlocked: BTreeSet<AchievementId>,
+}
+
+impl Achievements {
+    /// Current progress and the goal of an achievement.
+    pub fn progress(&self, id: AchievementId) -> (u128, u128) {
+        let (value, goal) = match id {
+            AchievementId::FirstWin => (self.wins, 1),
+            AchievementId::Level10 => (self.best_level, 10),
+            AchievementId::Coins1000 => (self.most_coins, 1000),
+            AchievementId::BuyEverything => {
+                let stock = shop_items();
+                let bought = stock
+                    .iter()
+                    .filter(|item| self.bought_items.contains(&item.name))
+                    .count();
+                (bought as u128, stock.len() as u128)
+            }
+        };
+        (value.min(goal), goal)
+    }
+
+    pub fn is_unlocked(&self, id: AchievementId) -> bool {
+        self.unlocked.contains(&id)
+    }
+
+    /// Records an event and returns the achievements it unlocked.
+    pub fn record(&mut self, event: GameEvent) -> Vec<&'static Achievement> {
+        match event {
+            GameEvent::BattleWon => self.wins = self.wins.saturating_add(1),
+            GameEvent::LevelReached(level) => self.best_level = self.best_level.max(level),
+            GameEvent::CoinsChanged(coins) => self.most_coins = self.most_coins.max(coins),
+            GameEvent::ItemBought(name) => {
+                self.bought_items.insert(name.into());
+            }
+        }
+        let mut unlocked = vec![];
+        for achievement in &ACHIEVEMENTS {
+            let (value, goal) = self.progress(achievement.id);
+            if value >= goal && self.unlocked.insert(achievement.id) {
+                unlocked.push(achievement);
+            }
+        }
+        unlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn achievements_unlock_only_once() {
+        let mut achievements = Achievements::default();
+        assert!(achievements.record(GameEvent::CoinsChanged(999)).is_empty());
+        let unlocked = achievements.record(GameEvent::CoinsChanged(1500));
+        assert_eq!(unlocked.len(), 1);
+        assert!(achievements.is_unlocked(AchievementId::Coins1000));
+        assert!(achievements
+            .record(GameEvent::CoinsChanged(2000))
+            .is_empty());
+    }
+
+    #[test]
+    fn buying_every_item_unlocks_big_spender() {
+        let mut achievements = Achievements::default();
+        for item in shop_items() {
+            achievements.record(GameEvent::ItemBought(&item.name));
+            achievements.record(GameEvent::ItemBought(&item.name));
+        }
+        assert!(achievements.is_unlocked(AchievementId::BuyEverything));
+        assert!(!achievements.is_unlocked(AchievementId::FirstWin));
+    }
+}
@@ -0,0 +1,262 @@
+//! Arenas for the platformer battle. Handcrafted ones are plain text files:
+//! `key: value` metadata, a `---` line, then the tile map.
+//!
+//! ```text
+//! name: Pit
+//! description: Two ledges over a pit
+//! ---
+//! #..........#
+//! #P........E#
+//! ####....####
+//! ```
+//!
+//! `#` is a wall, `.` is empty space, `P` marks where the player spawns and
+//! every `E` is an enemy spawn. `-` is a platform that can be jumped through
+//! from below, `H` a ladder, `^` spikes, `%` a breakable block and `*` a
+//! bounce pad. Bundled arenas live in `assets/arenas`, more can be dropped
+//! into the `arenas` folder next to the save.
+
+use super::utils::read_data_dir;
+
+pub const MIN_WIDTH: usize = 10;
+pub const MIN_HEIGHT: usize = 5;
+/// Arenas larger than the terminal scroll, these only keep files sane.
+pub const MAX_WIDTH: usize = 1000;
+pub const MAX_HEIGHT: usize = 200;
+const ARENAS_DIR: &str = "arenas";
+const BUNDLED_ARENAS: [&str; 4] = [
+    include_str!("../../assets/arenas/pit.txt"),
+    include_str!("../../assets/arenas/towers.txt"),
+    include_str!("../../assets/arenas/ledges.txt"),
+    include_str!("../../assets/arenas/caverns.txt"),
+];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Tile {
+    Empty,
+    Full,
+    /// Can be jumped through from below and stood on.
+    Platform,
+    Ladder,
+    /// Hurts whoever touches it.
+    Spikes,
+    /// Solid until hit by an attack.
+    Breakable,
+    /// Launches whoever lands on it high into the air.
+    Bounce,
+}
+
+impl Tile {
+    pub fn from_char(ch: char) -> Option<Tile> {
+        match ch {
+            '.' => Some(Tile::Empty),
+            '#' => Some(Tile::Full),
+            '-' => Some(Tile::Platform),
+            'H' => Some(Tile::Ladder),
+            '^' => Some(Tile::Spikes),
+            '%' => Some(Tile::Breakable),
+            '*' => Some(Tile::Bounce),
+            _ => None,
+        }
+    }
+
+    /// Whether the tile blocks movement from every side.
+    pub fn is_solid(&self) -> bool {
+        matches!(self, Tile::Full | Tile::Breakable | Tile::Bounce)
+    }
+}
+
+#[derive(Clone)]
+pub struct Arena {
+    pub name: String,
+    pub description: String,
+    pub width: usize,
+    pub height: usize,
+    /// Rows of tiles, top to bottom.
+    pub tiles: Vec<Vec<Tile>>,
+    pub player_spawn: (usize, usize),
+    pub enemy_spawns: Vec<(usize, usize)>,
+}
+
+impl Arena {
+    pub fn parse(text: &str) -> Result<Arena, String> {
+        let mut lines = text.lines().map(|line| line.trim_end_matches('\r'));
+        let mut name = None;
+        let mut description = String::new();
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(format!("Expected `key: value`, found `{}`", line));
+            };
+            match key.trim() {
+                "name" => name = Some(value.trim().to_string()),
+                "description" => description = value.trim().into(),
+                key => return Err(format!("Unknown key `{}`", key)),
+            }
+        }
+        let name = match name {
+            Some(name) if !name.is_empty() => name,
+            _ => return Err("Arena has no name".into()),
+        };
+
+        let mut rows: Vec<&str> = lines.collect();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
+        }
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if !(MIN_WIDTH..=MAX_WIDTH).contains(&width) || !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height)
+        {
+            return Err(format!(
+                "{}: map is {}x{}, it must be between {}x{} and {}x{}",
+                name, width, height, MIN_WIDTH, MIN_HEIGHT, MAX_WIDTH, MAX_HEIGHT
+            ));
+        }
+
+        let mut tiles = vec![];
+        let mut player_spawns = vec![];
+        let mut enemy_spawns = vec![];
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!(
+                    "{}: row {} isn't {} tiles wide",
+                    name,
+                    y + 1,
+                    width
+                ));
+            }
+            let mut tile_row = vec![];
+            for (x, ch) in row.chars().enumerate() {
+                let tile = match ch {
+                    'P' => {
+                        player_spawns.push((x, y));
+                        Tile::Empty
+                    }
+                    'E' => {
+                        enemy_spawns.push((x, y));
+                        Tile::Empty
+                    }
+                    ch => match Tile::from_char(ch) {
+                        Some(tile) => tile,
+                        None => {
+                            return Err(format!(
+                                "{}: unknown tile `{}` at row {}, column {}",
+                                name,
+                                ch,
+                                y + 1,
+                                x + 1
+                            ))
+                        }
+                    },
+                };
+                tile_row.push(tile);
+            }
+            tiles.push(tile_row);
+        }
+        let [player_spawn] = player_spawns[..] else {
+            return Err(format!("{}: map needs exactly one player spawn", name));
+        };
+        if enemy_spawns.is_empty() {
+            return Err(format!("{}: map needs at least one enemy spawn", name));
+        }
+
+        Ok(Arena {
+            name,
+            description,
+            width,
+            height,
+            tiles,
+            player_spawn,
+            enemy_spawns,
+        })
+    }
+}
+
+/// Bundled arenas followed by the player's own, along with errors for the
+/// files that failed to load.
+pub fn load_arenas() -> (Vec<Arena>, Vec<String>) {
+    let mut arenas: Vec<Arena> = vec![];
+    let mut errors = vec![];
+    let bundled = BUNDLED_ARENAS
+        .iter()
+        .map(|text| ("bundled".to_string(), text.to_string()));
+    for (file, text) in bundled.chain(read_data_dir(ARENAS_DIR)) {
+        match Arena::parse(&text) {
+            Ok(arena) if arenas.iter().any(|other| other.name == arena.name) => {
+                errors.push(format!("{}: arena {} already exists", file, arena.name))
+            }
+            Ok(arena) => arenas.push(arena),
+            Err(err) => errors.push(format!("{}: {}", file, err)),
+        }
+    }
+    (arenas, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "name: Test\ndescription: A test arena\n---\n\
+                         ..........\n\
+                         .P......E.\n\
+                         ..........\n\
+                         .-H.##%*^.\n\
+                         ##########\n";
+
+    #[test]
+    fn parses_valid_arena() {
+        let arena = Arena::parse(VALID).unwrap();
+        assert_eq!(arena.name, "Test");
+        assert_eq!(arena.description, "A test arena");
+        assert_eq!((arena.width, arena.height), (10, 5));
+        assert_eq!(arena.player_spawn, (1, 1));
+        assert_eq!(arena.enemy_spawns, vec![(8, 1)]);
+        assert_eq!(
+            arena.tiles[3],
+            [
+                Tile::Empty,
+                Tile::Platform,
+                Tile::Ladder,
+                Tile::Empty,
+                Tile::Full,
+                Tile::Full,
+                Tile::Breakable,
+                Tile::Bounce,
+                Tile::Spikes,
+                Tile::Empty,
+            ]
+        );
+        assert_eq!(arena.tiles[1][1], Tile::Empty);
+    }
+
+    #[test]
+    fn bundled_arenas_are_valid() {
+        for text in BUNDLED_ARENAS {
+            if let Err(err) = Arena::parse(text) {
+                panic!("{}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_arenas() {
+        let cases = [
+            VALID.replace("name: Test\n", ""),
+            VALID.replace("name:", "title:"),
+            VALID.replace(".P......E.", ".P......E"),
+            VALID.replace(".P......E.", ".P......E?"),
+            VALID.replace(".P......E.", "........E."),
+            VALID.replace(".P......E.", ".P....P.E."),
+            VALID.replace(".P......E.", ".P........"),
+            VALID.replace(".-H.##%*^.\n", ""),
+        ];
+        for case in cases {
+            assert!(Arena::parse(&case).is_err(), "accepted:\n{}", case);
+        }
+    }
+}
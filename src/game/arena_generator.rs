@@ -0,0 +1,249 @@
+//! Procedural arenas. The same seed and difficulty always give the same
+//! arena, and every arena it hands out lets the player walk and jump from
+//! their spawn to every enemy spawn.
+
+use super::{
+    arena::{Arena, Tile},
+    difficulty::Difficulty,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::{HashSet, VecDeque};
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 33;
+/// Top row of the ground.
+const FLOOR: usize = 30;
+/// Columns at both ends that are kept flat for the spawns.
+const SPAWN_ZONE: usize = 10;
+const MAX_ENEMY_SPAWNS: usize = 3;
+/// Layouts tried before settling for a flat floor, which is always fine.
+const ATTEMPTS: u32 = 20;
+
+/// How far a jump can take someone from where they stand, in cells.
+#[derive(Clone, Copy)]
+pub struct JumpReach {
+    pub up: usize,
+    pub across: usize,
+}
+
+struct Settings {
+    pits: std::ops::RangeInclusive<usize>,
+    spikes: bool,
+    platforms: usize,
+}
+
+fn settings(difficulty: Difficulty) -> Settings {
+    match difficulty {
+        Difficulty::Easy => Settings {
+            pits: 0..=1,
+            spikes: false,
+            platforms: 14,
+        },
+        Difficulty::Normal => Settings {
+            pits: 2..=3,
+            spikes: true,
+            platforms: 12,
+        },
+        Difficulty::Hard => Settings {
+            pits: 4..=6,
+            spikes: true,
+            platforms: 10,
+        },
+    }
+}
+
+pub fn generate(seed: u64, difficulty: Difficulty, reach: JumpReach) -> Arena {
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..ATTEMPTS {
+        let tiles = layout(&mut rng, &settings(difficulty), reach);
+        if let Some(arena) = with_spawns(&mut rng, tiles, reach) {
+            return arena;
+        }
+    }
+    let flat = (0..HEIGHT)
+        .map(|y| vec![if y < FLOOR { Tile::Empty } else { Tile::Full }; WIDTH])
+        .collect();
+    with_spawns(&mut rng, flat, reach).expect("a flat floor is always walkable")
+}
+
+fn layout(rng: &mut StdRng, settings: &Settings, reach: JumpReach) -> Vec<Vec<Tile>> {
+    let mut tiles = vec![vec![Tile::Empty; WIDTH]; HEIGHT];
+    for row in &mut tiles[FLOOR..] {
+        row.fill(Tile::Full);
+    }
+
+    let max_pit = reach.across.saturating_sub(1).max(2);
+    for _ in 0..rng.gen_range(settings.pits.clone()) {
+        let width = rng.gen_range(2..=max_pit);
+        let x = rng.gen_range(SPAWN_ZONE..WIDTH - SPAWN_ZONE - width);
+        for column in x..x + width {
+            for row in &mut tiles[FLOOR..HEIGHT - 1] {
+                row[column] = Tile::Empty;
+            }
+            if settings.spikes {
+                tiles[HEIGHT - 2][column] = Tile::Spikes;
+            }
+        }
+    }
+
+    // Platforms come in chains climbing from the floor, each one a jump
+    // away from the last, so most of them can be used.
+    let mut placed = 0;
+    while placed < settings.platforms {
+        let mut x = rng.gen_range(0..WIDTH) as i64;
+        let mut y = FLOOR;
+        let chain_end = placed + rng.gen_range(2..=4);
+        while placed < settings.platforms.min(chain_end) && y > 8 + reach.up {
+            let width = rng.gen_range(4..10);
+            y -= rng.gen_range(2..=reach.up.max(2));
+            x += rng.gen_range(-(reach.across as i64)..=reach.across as i64);
+            x = x.clamp(0, (WIDTH - width) as i64);
+            let tile = if rng.gen_bool(0.5) {
+                Tile::Full
+            } else {
+                Tile::Platform
+            };
+            for cell in &mut tiles[y][x as usize..x as usize + width] {
+                if *cell == Tile::Empty {
+                    *cell = tile;
+                }
+            }
+            placed += 1;
+        }
+    }
+    tiles
+}
+
+/// Puts the player on the left end of the floor and the enemies somewhere
+/// in the right third they can be reached. `None` if there is no such place.
+fn with_spawns(rng: &mut StdRng, tiles: Vec<Vec<Tile>>, reach: JumpReach) -> Option<Arena> {
+    let player_spawn = (rng.gen_range(2..SPAWN_ZONE - 2), FLOOR - 1);
+    let mut reachable: Vec<_> = reachable_from(&tiles, player_spawn, reach)
+        .into_iter()
+        .filter(|&(x, _)| x >= WIDTH * 2 / 3)
+        .collect();
+    if reachable.is_empty() {
+        return None;
+    }
+    // Sorted first, so the order of the set can't change the outcome.
+    reachable.sort();
+    let mut enemy_spawns = vec![];
+    for _ in 0..MAX_ENEMY_SPAWNS.min(reachable.len()) {
+        enemy_spawns.push(reachable.swap_remove(rng.gen_range(0..reachable.len())));
+    }
+
+    Some(Arena {
+        name: "Random".into(),
+        description: "A new arena for every fight".into(),
+        width: WIDTH,
+        height: HEIGHT,
+        tiles,
+        player_spawn,
+        enemy_spawns,
+    })
+}
+
+fn tile(tiles: &[Vec<Tile>], x: i64, y: i64) -> Option<Tile> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+    tiles.get(y as usize)?.get(x as usize).copied()
+}
+
+/// Whether someone can be in this cell without getting hurt.
+fn passable(tiles: &[Vec<Tile>], x: i64, y: i64) -> bool {
+    tile(tiles, x, y).is_some_and(|tile| !tile.is_solid() && tile != Tile::Spikes)
+}
+
+fn standing(tiles: &[Vec<Tile>], x: i64, y: i64) -> bool {
+    passable(tiles, x, y)
+        && tile(tiles, x, y + 1).is_none_or(|below| below.is_solid() || below == Tile::Platform)
+}
+
+/// Every cell the player can stand in, starting from `start`. Jumps are
+/// modelled as going straight up and then across, which is stricter than
+/// the arc the player can actually steer in the air.
+pub fn reachable_from(
+    tiles: &[Vec<Tile>],
+    start: (usize, usize),
+    reach: JumpReach,
+) -> HashSet<(usize, usize)> {
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([(start.0 as i64, start.1 as i64)]);
+    let column_clear =
+        |x: i64, from: i64, to: i64| (from.min(to)..=from.max(to)).all(|y| passable(tiles, x, y));
+    let row_clear =
+        |y: i64, from: i64, to: i64| (from.min(to)..=from.max(to)).all(|x| passable(tiles, x, y));
+    let (up, across) = (reach.up as i64, reach.across as i64);
+
+    while let Some((x, y)) = queue.pop_front() {
+        if !standing(tiles, x, y) || !visited.insert((x as usize, y as usize)) {
+            continue;
+        }
+        for to_x in x - across..=x + across {
+            for to_y in y - up..=y {
+                if standing(tiles, to_x, to_y)
+                    && column_clear(x, y, to_y)
+                    && row_clear(to_y, x, to_x)
+                {
+                    queue.push_back((to_x, to_y));
+                }
+            }
+            // Walking or jumping off a ledge and dropping down.
+            if row_clear(y, x, to_x) {
+                let mut to_y = y;
+                while passable(tiles, to_x, to_y) && !standing(tiles, to_x, to_y) {
+                    to_y += 1;
+                }
+                queue.push_back((to_x, to_y));
+            }
+        }
+    }
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::physics::jump_reach;
+    use proptest::prelude::*;
+
+    #[test]
+    fn same_seed_gives_same_arena() {
+        let first = generate(42, Difficulty::Hard, jump_reach());
+        let second = generate(42, Difficulty::Hard, jump_reach());
+        assert!(first.tiles == second.tiles);
+        assert_eq!(first.player_spawn, second.player_spawn);
+        assert_eq!(first.enemy_spawns, second.enemy_spawns);
+    }
+
+    #[test]
+    fn walls_too_high_to_jump_are_unreachable() {
+        let rows = ["......", "..#...", "..#...", "..#...", "..#...", "######"];
+        let tiles: Vec<Vec<Tile>> = rows
+            .iter()
+            .map(|row| row.chars().map(|ch| Tile::from_char(ch).unwrap()).collect())
+            .collect();
+        let reach = JumpReach { up: 3, across: 5 };
+        let reachable = reachable_from(&tiles, (0, 4), reach);
+        assert!(reachable.contains(&(1, 4)));
+        assert!(!reachable.contains(&(3, 4)));
+
+        let higher = JumpReach { up: 4, across: 5 };
+        assert!(reachable_from(&tiles, (0, 4), higher).contains(&(3, 4)));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn enemies_are_reachable_from_the_player(seed: u64, difficulty in 0usize..3) {
+            let arena = generate(seed, Difficulty::ALL[difficulty], jump_reach());
+            let reachable = reachable_from(&arena.tiles, arena.player_spawn, jump_reach());
+            prop_assert!(!arena.enemy_spawns.is_empty());
+            for spawn in [arena.player_spawn].iter().chain(&arena.enemy_spawns) {
+                prop_assert!(reachable.contains(spawn));
+            }
+        }
+    }
+}
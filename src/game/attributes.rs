@@ -0,0 +1,81 @@
+use super::numeric::{cap, capped_add, capped_mul};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct Attributes {
+    pub strength: u128,
+    pub vitality: u128,
+    pub agility: u128,
+    pub luck: u128,
+}
+
+impl Attributes {
+    pub const NAMES: [&'static str; 4] = ["Strength", "Vitality", "Agility", "Luck"];
+
+    pub fn get(&self, index: usize) -> u128 {
+        match index {
+            0 => self.strength,
+            1 => self.vitality,
+            2 => self.agility,
+            _ => self.luck,
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> &mut u128 {
+        match index {
+            0 => &mut self.strength,
+            1 => &mut self.vitality,
+            2 => &mut self.agility,
+            _ => &mut self.luck,
+        }
+    }
+
+    pub fn total(&self) -> u128 {
+        capped_add(
+            capped_add(self.strength, self.vitality),
+            capped_add(self.agility, self.luck),
+        )
+    }
+
+    pub fn add(&mut self, other: &Attributes) {
+        self.strength = capped_add(self.strength, other.strength);
+        self.vitality = capped_add(self.vitality, other.vitality);
+        self.agility = capped_add(self.agility, other.agility);
+        self.luck = capped_add(self.luck, other.luck);
+    }
+
+    pub fn sanitize(&mut self) {
+        for index in 0..Attributes::NAMES.len() {
+            let value = self.get_mut(index);
+            *value = cap(*value);
+        }
+    }
+
+    pub fn bonus_health(&self) -> u128 {
+        capped_mul(self.vitality, 10)
+    }
+
+    pub fn bonus_damage(&self) -> u128 {
+        capped_mul(self.strength, 2)
+    }
+
+    /// Chance in percent to avoid an incoming attack.
+    pub fn dodge_chance(&self) -> u32 {
+        capped_mul(self.agility, 2).min(40) as u32
+    }
+
+    /// Chance in percent to deal double damage.
+    pub fn crit_chance(&self) -> u32 {
+        capped_mul(self.luck, 2).min(50) as u32
+    }
+
+    /// Short description of what a single point in the attribute gives.
+    pub fn describe(index: usize) -> &'static str {
+        match index {
+            0 => "+2 damage",
+            1 => "+10 health",
+            2 => "+2% dodge chance (max 40%)",
+            _ => "+2% critical hit chance (max 50%)",
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use super::{
+    class::Class,
+    numeric::{capped_add, capped_mul},
+    utils::read_data_file,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::OnceLock};
+
+const DEFAULT_BALANCE: &str = include_str!("../../assets/balance.json");
+const BALANCE_FILE: &str = "balance.json";
+/// Validation walks every level, so the cap has to stay small enough for
+/// that to be instant.
+const MAX_LEVEL_CAP: u128 = 10_000;
+
+static BALANCE: OnceLock<(BalanceConfig, Option<String>)> = OnceLock::new();
+
+/// `base + linear * level + quadratic * level^2`
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Curve {
+    pub base: u128,
+    pub linear: u128,
+    pub quadratic: u128,
+}
+
+impl Curve {
+    pub fn at(&self, level: u128) -> u128 {
+        let linear = capped_mul(self.linear, level);
+        let quadratic = capped_mul(capped_mul(self.quadratic, level), level);
+        capped_add(capped_add(self.base, linear), quadratic)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct StatGrowth {
+    pub health: Curve,
+    pub damage: Curve,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Reward {
+    pub xp: u128,
+    pub coins: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Rewards {
+    pub win: Reward,
+    pub loss: Reward,
+}
+
+/// What the player loses after being defeated in a battle.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct DefeatPenalty {
+    pub coin_loss_percent: u128,
+    /// Only the progress towards the next level is lost, never levels.
+    pub xp_loss_percent: u128,
+    /// Durability taken from every equipped item.
+    pub durability_loss: u32,
+}
+
+impl Default for DefeatPenalty {
+    fn default() -> Self {
+        bundled().defeat_penalty
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BalanceConfig {
+    pub level_cap: u128,
+    pub xp_curve: Curve,
+    pub stat_points_per_level: u128,
+    pub skill_points_per_level: u128,
+    /// Missing from balance files written before resting at the inn.
+    #[serde(default = "default_rest_cost_per_level")]
+    pub rest_cost_per_level: u128,
+    pub class_growth: BTreeMap<Class, StatGrowth>,
+    /// Missing from balance files written before defeat penalties.
+    #[serde(default)]
+    pub defeat_penalty: DefeatPenalty,
+    pub rewards: Rewards,
+}
+
+/// The bundled config, used to fill in fields older custom files don't have.
+fn bundled() -> BalanceConfig {
+    serde_json::from_str(DEFAULT_BALANCE).unwrap()
+}
+
+fn default_rest_cost_per_level() -> u128 {
+    bundled().rest_cost_per_level
+}
+
+impl BalanceConfig {
+    fn parse(content: &str) -> Result<BalanceConfig, String> {
+        let config: BalanceConfig = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the level cap is sane, every class has growth, the xp
+    /// requirement keeps growing and stats never go down until the level cap.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.level_cap == 0 {
+            return Err("level_cap must be at least 1".into());
+        }
+        if self.level_cap > MAX_LEVEL_CAP {
+            return Err(format!("level_cap can't be above {}", MAX_LEVEL_CAP));
+        }
+        if self.xp_curve.at(1) == 0 {
+            return Err("xp_curve must require some xp to level up".into());
+        }
+        let penalty = &self.defeat_penalty;
+        if penalty.coin_loss_percent > 100 || penalty.xp_loss_percent > 100 {
+            return Err("defeat_penalty percents can't be above 100".into());
+        }
+        let mut curves = vec![];
+        for class in Class::ALL {
+            let Some(growth) = self.class_growth.get(&class) else {
+                return Err(format!("class_growth is missing {}", class.name()));
+            };
+            curves.push((format!("{} health", class.name()), growth.health));
+            curves.push((format!("{} damage", class.name()), growth.damage));
+        }
+        for level in 1..self.level_cap {
+            if self.xp_curve.at(level + 1) <= self.xp_curve.at(level) {
+                return Err(format!("xp_curve doesn't grow at level {}", level + 1));
+            }
+        }
+        for (name, curve) in curves {
+            for level in 1..self.level_cap {
+                if curve.at(level + 1) < curve.at(level) {
+                    return Err(format!("{} goes down at level {}", name, level + 1));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn growth(&self, class: Class) -> StatGrowth {
+        self.class_growth[&class]
+    }
+}
+
+/// The custom balance config from the save directory, or the bundled one
+/// with the reason the custom file was rejected.
+fn load_balance() -> (BalanceConfig, Option<String>) {
+    match read_data_file(BALANCE_FILE).map(|content| BalanceConfig::parse(&content)) {
+        Some(Ok(config)) => (config, None),
+        Some(Err(err)) => (
+            bundled(),
+            Some(format!(
+                "Invalid balance config, using the default one. {}",
+                err
+            )),
+        ),
+        None => (bundled(), None),
+    }
+}
+
+/// Balance config loaded from the save directory, falling back to the
+/// bundled defaults when there is no valid custom file.
+pub fn balance() -> &'static BalanceConfig {
+    &BALANCE.get_or_init(load_balance).0
+}
+
+/// Why the custom balance config was ignored, if it was.
+pub fn balance_error() -> Option<&'static str> {
+    BALANCE.get_or_init(load_balance).1.as_deref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_config() -> BalanceConfig {
+        BalanceConfig::parse(DEFAULT_BALANCE).unwrap()
+    }
+
+    #[test]
+    fn default_curves_are_monotonic() {
+        let config = default_config();
+        for level in 1..config.level_cap {
+            assert!(config.xp_curve.at(level + 1) > config.xp_curve.at(level));
+            for class in Class::ALL {
+                let growth = config.growth(class);
+                assert!(growth.health.at(level + 1) >= growth.health.at(level));
+                assert!(growth.damage.at(level + 1) >= growth.damage.at(level));
+            }
+        }
+    }
+
+    #[test]
+    fn default_curves_match_original_formulas() {
+        let config = default_config();
+        let warrior = config.growth(Class::Warrior);
+        for level in 1..config.level_cap {
+            assert_eq!(config.xp_curve.at(level), level.pow(2) * 40 + 60);
+            assert_eq!(warrior.health.at(level), level * 30 + 100);
+            assert_eq!(warrior.damage.at(level), level * 2 + 10);
+        }
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_bundled_values() {
+        let mut json: serde_json::Value = serde_json::from_str(DEFAULT_BALANCE).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("rest_cost_per_level");
+        fields.remove("defeat_penalty");
+        let config = BalanceConfig::parse(&json.to_string()).unwrap();
+        let bundled = default_config();
+        assert_eq!(config.rest_cost_per_level, bundled.rest_cost_per_level);
+        let (penalty, expected) = (config.defeat_penalty, bundled.defeat_penalty);
+        assert_eq!(penalty.coin_loss_percent, expected.coin_loss_percent);
+        assert_eq!(penalty.xp_loss_percent, expected.xp_loss_percent);
+        assert_eq!(penalty.durability_loss, expected.durability_loss);
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let mut config = default_config();
+        config.level_cap = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = default_config();
+        config.level_cap = u128::MAX;
+        assert!(config.validate().is_err());
+
+        let mut config = default_config();
+        config.xp_curve = Curve {
+            base: 0,
+            linear: 0,
+            quadratic: 0,
+        };
+        assert!(config.validate().is_err());
+
+        let mut config = default_config();
+        config.xp_curve = Curve {
+            base: 100,
+            linear: 0,
+            quadratic: 0,
+        };
+        assert!(config.validate().is_err());
+
+        let mut config = default_config();
+        config.class_growth.remove(&Class::Mage);
+        assert!(config.validate().is_err());
+    }
+}
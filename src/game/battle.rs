@@ -1,29 +1,72 @@
 #![allow(unused)]
-use super::{equipment::Equipment, player::Player};
+use super::{
+    difficulty::Difficulty,
+    equipment::Equipment,
+    item::ConsumableEffect,
+    numeric::{cap, capped_add, capped_mul},
+    player::Player,
+    skill_tree::ActiveSkill,
+};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Entity {
     health: u128,
+    max_health: u128,
     damage: u128,
     name: String,
     equipment: Equipment,
+    #[serde(default)]
+    dodge_chance: u32,
+    #[serde(default)]
+    crit_chance: u32,
+    #[serde(default)]
+    defence: u128,
 }
 
 impl Entity {
     pub fn new(hp: u128, dmg: u128, name: &str, equipment: Option<Equipment>) -> Entity {
-        let equip = match equipment {
-            Some(e) => e,
-            None => Equipment::default(),
-        };
+        let equip = equipment.unwrap_or_default();
         Entity {
-            health: hp,
-            damage: dmg,
+            health: cap(hp),
+            max_health: cap(hp),
+            damage: cap(dmg),
             name: name.into(),
             equipment: equip,
+            dodge_chance: 0,
+            crit_chance: 0,
+            defence: 0,
         }
     }
 
+    /// Sets dodge and critical hit chances in percent.
+    pub fn with_chances(mut self, dodge_chance: u32, crit_chance: u32) -> Entity {
+        self.dodge_chance = dodge_chance;
+        self.crit_chance = crit_chance;
+        self
+    }
+
+    /// Starts the entity wounded, never above its maximum health.
+    pub fn with_current_health(mut self, health: u128) -> Entity {
+        self.health = health.min(self.max_health);
+        self
+    }
+
+    /// Sets defence on top of whatever the equipment gives.
+    pub fn with_defence(mut self, defence: u128) -> Entity {
+        self.defence = cap(defence);
+        self
+    }
+
+    /// Scales health and damage to the given difficulty.
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Entity {
+        self.max_health = difficulty.scale_enemy(self.max_health).max(1);
+        self.health = difficulty.scale_enemy(self.health).max(1);
+        self.damage = difficulty.scale_enemy(self.damage);
+        self
+    }
+
     pub fn get_name(&self) -> &str {
         &self.name
     }
@@ -31,15 +74,46 @@ impl Entity {
     pub fn get_health(&self) -> u128 {
         self.health
     }
+
+    pub fn get_max_health(&self) -> u128 {
+        self.max_health
+    }
+
+    pub fn get_equipment(&self) -> &Equipment {
+        &self.equipment
+    }
+
+    /// Damage that ignores defence and dodging, like falling onto spikes.
+    pub fn take_damage(&mut self, amount: u128) {
+        self.health = self.health.saturating_sub(amount);
+    }
+
+    /// Never above maximum health.
+    pub fn heal(&mut self, amount: u128) {
+        self.health = capped_add(self.health, amount).min(self.max_health);
+    }
+
+    /// Heals or buffs the entity, escaping is up to the battle.
+    pub fn use_consumable(&mut self, effect: &ConsumableEffect) {
+        match effect {
+            ConsumableEffect::Heal(amount) => self.heal(*amount),
+            ConsumableEffect::Buff(amount) => self.damage = capped_add(self.damage, *amount),
+            ConsumableEffect::Escape => (),
+        }
+    }
 }
 
 impl Default for Entity {
     fn default() -> Entity {
         Entity {
             health: 100,
+            max_health: 100,
             damage: 10,
             name: "Dummy".into(),
             equipment: Equipment::default(),
+            dodge_chance: 0,
+            crit_chance: 0,
+            defence: 0,
         }
     }
 }
@@ -61,6 +135,8 @@ pub struct Battle {
     player_turn: bool,
     #[serde(skip_serializing, skip_deserializing)]
     winner: Option<BattleWinner>,
+    #[serde(skip_serializing, skip_deserializing)]
+    damage_dealt: u128,
 }
 
 impl Battle {
@@ -70,6 +146,7 @@ impl Battle {
             enemy: enemy.clone(),
             player_turn: true,
             winner: None,
+            damage_dealt: 0,
         }
     }
 
@@ -81,44 +158,130 @@ impl Battle {
         self.winner
     }
 
-    pub fn tick(&mut self) {
-        if self.player_turn {
-            let mut damage = self.player.damage;
-            match &self.player.equipment.sword {
-                Some(sword) => damage += sword.properties.damage,
-                None => (),
+    /// Total damage the player dealt to the enemy so far.
+    pub fn get_damage_dealt(&self) -> u128 {
+        self.damage_dealt
+    }
+
+    /// Resolves a single attack, wearing down the attacker's sword and the
+    /// defender's shield. Returns `true` if the defender was defeated.
+    pub fn attack(attacker: &mut Entity, defender: &mut Entity) -> bool {
+        let damage = Battle::roll_damage(attacker);
+        Battle::defend(defender, damage)
+    }
+
+    /// Damage of one blow of the attacker, crits included. Wears down their
+    /// sword, so a blow that hits several defenders should roll only once.
+    pub fn roll_damage(attacker: &mut Entity) -> u128 {
+        let mut damage = attacker.damage;
+        if let Some(sword) = &mut attacker.equipment.sword {
+            damage = capped_add(damage, sword.effective_properties().damage);
+            sword.wear();
+        }
+        if rand::thread_rng().gen_range(0..100) < attacker.crit_chance {
+            damage = capped_mul(damage, 2);
+        }
+        damage
+    }
+
+    /// Takes a blow of `damage` unless the defender dodges it, wearing down
+    /// their shield. Returns `true` if the defender was defeated.
+    pub fn defend(defender: &mut Entity, mut damage: u128) -> bool {
+        if rand::thread_rng().gen_range(0..100) < defender.dodge_chance {
+            return false;
+        }
+        let mut defence = capped_add(
+            defender.defence,
+            defender.equipment.set_bonus_stats().1.defence,
+        );
+        if let Some(shield) = &mut defender.equipment.shield {
+            defence = capped_add(defence, shield.effective_properties().defence);
+            shield.wear();
+        }
+        if damage > defence {
+            damage -= defence;
+            if defender.health <= damage {
+                defender.health = 0;
+                return true;
             }
-            let enemy_defence = match &self.enemy.equipment.shield {
-                Some(shield) => shield.properties.defence,
-                None => 0,
-            };
-            if damage > enemy_defence {
-                damage -= enemy_defence;
-                if self.enemy.health <= damage {
-                    self.winner = Some(BattleWinner::Player(self.player.health));
-                    return;
-                }
-                self.enemy.health -= damage;
+            defender.health -= damage;
+        }
+        false
+    }
+
+    /// Applies a consumable to the player, using up their turn.
+    pub fn use_consumable(&mut self, effect: &ConsumableEffect) {
+        if *effect == ConsumableEffect::Escape {
+            return;
+        }
+        self.player.use_consumable(effect);
+        self.player_turn = false;
+    }
+
+    /// Uses an active skill of the player, using up their turn.
+    pub fn use_active_skill(&mut self, skill: ActiveSkill) {
+        match skill {
+            ActiveSkill::PowerStrike => {
+                let damage = self.player.damage;
+                self.player.damage = capped_mul(damage, 2);
+                self.tick();
+                self.player.damage = damage;
             }
-        } else {
-            let mut damage = self.enemy.damage;
-            match &self.enemy.equipment.sword {
-                Some(sword) => damage += sword.properties.damage,
-                None => (),
+            ActiveSkill::SecondWind => {
+                self.player.heal(self.player.max_health / 3);
+                self.player_turn = false;
             }
-            let player_defence = match &self.player.equipment.shield {
-                Some(shield) => shield.properties.defence,
-                None => 0,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if self.player_turn {
+            let enemy_health = self.enemy.health;
+            if Battle::attack(&mut self.player, &mut self.enemy) {
+                self.damage_dealt = capped_add(self.damage_dealt, enemy_health);
+                self.winner = Some(BattleWinner::Player(self.player.health));
+                return;
+            }
+            self.damage_dealt = capped_add(self.damage_dealt, enemy_health - self.enemy.health);
+        } else if Battle::attack(&mut self.enemy, &mut self.player) {
+            self.winner = Some(BattleWinner::Enemy(self.enemy.health));
+            return;
+        }
+        self.player_turn = !self.player_turn;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn battle_always_ends_without_overflow(
+            player_hp in 1u128..,
+            player_dmg: u128,
+            enemy_hp in 1u128..,
+            enemy_dmg: u128,
+        ) {
+            let mut battle = Battle {
+                player: Entity::new(player_hp, player_dmg.max(1), "Player", None)
+                    .with_chances(0, 100),
+                enemy: Entity::new(enemy_hp, enemy_dmg.max(1), "Enemy", None),
+                player_turn: true,
+                winner: None,
+                damage_dealt: 0,
             };
-            if damage > player_defence {
-                damage -= player_defence;
-                if self.player.health <= damage {
-                    self.winner = Some(BattleWinner::Enemy(self.enemy.health));
-                    return;
+            battle.use_consumable(&ConsumableEffect::Buff(u128::MAX));
+            battle.use_consumable(&ConsumableEffect::Heal(u128::MAX));
+            battle.use_active_skill(ActiveSkill::SecondWind);
+            for _ in 0..100 {
+                if battle.get_winner().is_some() {
+                    break;
                 }
-                self.player.health -= damage;
+                battle.tick();
             }
+            prop_assert!(battle.player.get_health() <= battle.player.max_health);
         }
-        self.player_turn = !self.player_turn;
     }
 }
@@ -0,0 +1,131 @@
+use super::{
+    balance::balance,
+    item::{Item, ItemProperties, ItemType},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum SkillEffect {
+    BonusHealth(u128),
+    BonusDamage(u128),
+    BonusDefence(u128),
+    /// Extra critical hit chance in percent.
+    CritChance(u32),
+    /// Extra dodge chance in percent.
+    DodgeChance(u32),
+}
+
+pub struct ClassSkill {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub unlock_level: u128,
+    pub effect: SkillEffect,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Class {
+    #[default]
+    Warrior,
+    Rogue,
+    Mage,
+}
+
+impl Class {
+    pub const ALL: [Class; 3] = [Class::Warrior, Class::Rogue, Class::Mage];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Class::Warrior => "Warrior",
+            Class::Rogue => "Rogue",
+            Class::Mage => "Mage",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Class::Warrior => "Sturdy fighter, grows tough and starts with a sword and a shield",
+            Class::Rogue => "Quick and lucky, hits often and dodges a lot",
+            Class::Mage => "Fragile, but deals huge damage with arcane power",
+        }
+    }
+
+    /// Base health and damage of the class at the given level.
+    pub fn stats_from_level(&self, level: u128) -> (u128, u128) {
+        let growth = balance().growth(*self);
+        (growth.health.at(level), growth.damage.at(level))
+    }
+
+    pub fn starting_gear(&self) -> Vec<Item> {
+        let weapon = |name: &str, damage: u128| {
+            Item::new(
+                name,
+                ItemType::Sword,
+                5,
+                ItemProperties { damage, defence: 0 },
+            )
+        };
+        match self {
+            Class::Warrior => vec![
+                weapon("Rusty Sword", 4),
+                Item::new(
+                    "Wooden Shield",
+                    ItemType::Shield,
+                    5,
+                    ItemProperties {
+                        damage: 0,
+                        defence: 2,
+                    },
+                ),
+            ],
+            Class::Rogue => vec![weapon("Twin Daggers", 6)],
+            Class::Mage => vec![weapon("Apprentice Staff", 8)],
+        }
+    }
+
+    pub fn skills(&self) -> &'static [ClassSkill] {
+        match self {
+            Class::Warrior => &[
+                ClassSkill {
+                    name: "Thick Skin",
+                    description: "+25 health",
+                    unlock_level: 2,
+                    effect: SkillEffect::BonusHealth(25),
+                },
+                ClassSkill {
+                    name: "Shield Wall",
+                    description: "+3 defence",
+                    unlock_level: 5,
+                    effect: SkillEffect::BonusDefence(3),
+                },
+            ],
+            Class::Rogue => &[
+                ClassSkill {
+                    name: "Evasion",
+                    description: "+10% dodge chance",
+                    unlock_level: 2,
+                    effect: SkillEffect::DodgeChance(10),
+                },
+                ClassSkill {
+                    name: "Backstab",
+                    description: "+15% critical hit chance",
+                    unlock_level: 5,
+                    effect: SkillEffect::CritChance(15),
+                },
+            ],
+            Class::Mage => &[
+                ClassSkill {
+                    name: "Arcane Focus",
+                    description: "+5 damage",
+                    unlock_level: 2,
+                    effect: SkillEffect::BonusDamage(5),
+                },
+                ClassSkill {
+                    name: "Mana Shield",
+                    description: "+4 defence",
+                    unlock_level: 5,
+                    effect: SkillEffect::BonusDefence(4),
+                },
+            ],
+        }
+    }
+}
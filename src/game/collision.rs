@@ -0,0 +1,251 @@
+//! Swept collision of axis aligned boxes against a grid of solid tiles. Each
+//! axis is resolved separately, x first, and every cell the box passes
+//! through is checked, so fast movement can't tunnel through thin walls.
+
+/// Keeps boxes that touch an edge from counting as overlapping the next cell.
+const EPSILON: f64 = 1e-6;
+/// How far to probe when checking whether a box is touching a wall.
+const PROBE: f64 = 0.01;
+
+/// Box in grid cells, `x` and `y` are its top left corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Aabb {
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+/// Sides of the box that were stopped by a solid tile.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Collisions {
+    pub left: bool,
+    pub right: bool,
+    pub top: bool,
+    pub bottom: bool,
+}
+
+impl Collisions {
+    pub fn any(&self) -> bool {
+        self.left || self.right || self.top || self.bottom
+    }
+}
+
+/// Cells covered by the span `start..start + length`.
+fn cells(start: f64, length: f64) -> std::ops::RangeInclusive<i64> {
+    (start + EPSILON).floor() as i64..=(start + length - EPSILON).floor() as i64
+}
+
+/// Moves `start..start + length` by `delta` along one axis. `blocked` tells
+/// whether a cell on that axis is solid anywhere across the box. Returns the
+/// new start and whether the move was stopped.
+fn sweep(start: f64, length: f64, delta: f64, blocked: impl Fn(i64) -> bool) -> (f64, bool) {
+    if delta > 0.0 {
+        let first = (start + length - EPSILON).floor() as i64 + 1;
+        let last = (start + length + delta - EPSILON).floor() as i64;
+        for cell in first..=last {
+            if blocked(cell) {
+                return (cell as f64 - length, true);
+            }
+        }
+    } else if delta < 0.0 {
+        let first = (start + EPSILON).floor() as i64 - 1;
+        let last = (start + delta + EPSILON).floor() as i64;
+        for cell in (last..=first).rev() {
+            if blocked(cell) {
+                return (cell as f64 + 1.0, true);
+            }
+        }
+    }
+    (start + delta, false)
+}
+
+/// Moves the box by `dx`, then by `dy`, stopping flush against the first
+/// solid cell on each axis.
+pub fn move_and_collide(
+    is_solid: impl Fn(i64, i64) -> bool,
+    aabb: Aabb,
+    dx: f64,
+    dy: f64,
+) -> (Aabb, Collisions) {
+    let mut aabb = aabb;
+    let mut collisions = Collisions::default();
+
+    let rows = cells(aabb.y, aabb.height);
+    let (x, hit) = sweep(aabb.x, aabb.width, dx, |column| {
+        rows.clone().any(|row| is_solid(column, row))
+    });
+    aabb.x = x;
+    collisions.right = hit && dx > 0.0;
+    collisions.left = hit && dx < 0.0;
+
+    let columns = cells(aabb.x, aabb.width);
+    let (y, hit) = sweep(aabb.y, aabb.height, dy, |row| {
+        columns.clone().any(|column| is_solid(column, row))
+    });
+    aabb.y = y;
+    collisions.bottom = hit && dy > 0.0;
+    collisions.top = hit && dy < 0.0;
+
+    (aabb, collisions)
+}
+
+/// Grid cells the box overlaps, touching an edge doesn't count.
+pub fn covered_cells(aabb: Aabb) -> impl Iterator<Item = (i64, i64)> {
+    let rows = cells(aabb.y, aabb.height);
+    cells(aabb.x, aabb.width).flat_map(move |x| rows.clone().map(move |y| (x, y)))
+}
+
+/// Whether moving the box a tiny bit by the given direction would collide.
+pub fn touches(is_solid: impl Fn(i64, i64) -> bool, aabb: Aabb, dx: f64, dy: f64) -> bool {
+    move_and_collide(is_solid, aabb, dx * PROBE, dy * PROBE)
+        .1
+        .any()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a grid from rows of `#` (solid) and `.` (empty). Everything
+    /// outside of it is solid.
+    fn grid<'a>(rows: &'a [&'a str]) -> impl Fn(i64, i64) -> bool + 'a {
+        move |x, y| {
+            if x < 0 || y < 0 {
+                return true;
+            }
+            rows.get(y as usize)
+                .and_then(|row| row.as_bytes().get(x as usize))
+                .is_none_or(|&cell| cell == b'#')
+        }
+    }
+
+    fn unit(x: f64, y: f64) -> Aabb {
+        Aabb {
+            x,
+            y,
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+
+    #[test]
+    fn fast_fall_does_not_tunnel_through_thin_platform() {
+        let rows = [
+            "....", //
+            "....", //
+            "....", //
+            "####", //
+            "....", //
+            "....", //
+        ];
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(1.0, 0.0), 0.0, 5.0);
+        assert_eq!(aabb, unit(1.0, 2.0));
+        assert!(hits.bottom && !hits.top);
+    }
+
+    #[test]
+    fn jump_stops_at_ceiling() {
+        let rows = [
+            "....", //
+            ".#..", //
+            "....", //
+            "....", //
+        ];
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(1.3, 3.0), 0.0, -2.5);
+        assert_eq!(aabb, unit(1.3, 2.0));
+        assert!(hits.top);
+    }
+
+    #[test]
+    fn walls_stop_both_directions() {
+        let rows = [
+            "#.....#", //
+        ];
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(2.5, 0.0), 10.0, 0.0);
+        assert_eq!(aabb, unit(5.0, 0.0));
+        assert!(hits.right && !hits.left);
+
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(2.5, 0.0), -10.0, 0.0);
+        assert_eq!(aabb, unit(1.0, 0.0));
+        assert!(hits.left && !hits.right);
+    }
+
+    #[test]
+    fn box_straddling_rows_hits_wall_in_either_row() {
+        let rows = [
+            ".....", //
+            "...#.", //
+            ".....", //
+        ];
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(0.0, 0.5), 3.0, 0.0);
+        assert_eq!(aabb, unit(2.0, 0.5));
+        assert!(hits.right);
+    }
+
+    #[test]
+    fn diagonal_move_into_corner_stops_on_both_axes() {
+        let rows = [
+            "....", //
+            "....", //
+            "..#.", //
+            "####", //
+        ];
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(0.5, 2.0), 2.0, 2.0);
+        assert_eq!(aabb, unit(1.0, 2.0));
+        assert_eq!(
+            hits,
+            Collisions {
+                left: false,
+                right: true,
+                top: false,
+                bottom: true,
+            }
+        );
+    }
+
+    #[test]
+    fn diagonal_move_slides_along_free_axis() {
+        let rows = [
+            "....", //
+            "....", //
+            "..#.", //
+            "####", //
+        ];
+        // Clears the step horizontally, then lands on top of it.
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(0.5, 1.0), 2.0, 2.0);
+        assert_eq!(aabb, unit(2.5, 1.0));
+        assert!(hits.bottom && !hits.right);
+    }
+
+    #[test]
+    fn grazing_an_edge_is_not_a_collision() {
+        let rows = [
+            "#...", //
+            "....", //
+        ];
+        // Slides along the bottom of the solid cell without touching it.
+        let (aabb, hits) = move_and_collide(grid(&rows), unit(3.0, 1.0), -3.0, 0.0);
+        assert_eq!(aabb, unit(0.0, 1.0));
+        assert!(!hits.any());
+        assert!(touches(grid(&rows), aabb, 0.0, 1.0));
+        assert!(!touches(grid(&rows), unit(1.0, 0.5), 1.0, 0.0));
+    }
+
+    #[test]
+    fn covered_cells_ignore_touching_edges() {
+        let cells: Vec<_> = covered_cells(unit(1.0, 2.0)).collect();
+        assert_eq!(cells, [(1, 2)]);
+        let cells: Vec<_> = covered_cells(unit(0.5, 2.0)).collect();
+        assert_eq!(cells, [(0, 2), (1, 2)]);
+    }
+}
@@ -0,0 +1,71 @@
+use super::item::Item;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+const RECIPES_DATA: &str = include_str!("../../assets/recipes.json");
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MaterialDrop {
+    pub name: String,
+    /// Chance in percent that the material drops after a won battle.
+    pub drop_chance: u32,
+    pub max_amount: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Recipe {
+    pub ingredients: Vec<(String, u128)>,
+    pub result: Item,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CraftingData {
+    pub materials: Vec<MaterialDrop>,
+    pub recipes: Vec<Recipe>,
+}
+
+impl CraftingData {
+    pub fn load() -> CraftingData {
+        match serde_json::from_str(RECIPES_DATA) {
+            Ok(data) => data,
+            Err(err) => panic!("Corrupted recipes data. {}", err),
+        }
+    }
+
+    pub fn roll_drops(&self, rng: &mut impl Rng) -> Vec<(String, u128)> {
+        let mut drops = vec![];
+        for material in &self.materials {
+            if rng.gen_range(0..100) < material.drop_chance {
+                let amount = rng.gen_range(1..=material.max_amount);
+                drops.push((material.name.clone(), amount));
+            }
+        }
+        drops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_recipes_parse() {
+        let data = CraftingData::load();
+        assert!(!data.recipes.is_empty());
+        for recipe in &data.recipes {
+            for (name, amount) in &recipe.ingredients {
+                assert!(*amount > 0);
+                assert!(
+                    data.materials.iter().any(|material| &material.name == name),
+                    "{} needs {} that never drops",
+                    recipe.result.name,
+                    name
+                );
+            }
+        }
+        for material in &data.materials {
+            assert!(material.max_amount > 0);
+            assert!(material.drop_chance <= 100);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::numeric::{cap, capped_mul};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Weaker enemies, smaller rewards",
+            Difficulty::Normal => "The way the game is meant to be played",
+            Difficulty::Hard => "Stronger enemies, bigger rewards",
+        }
+    }
+
+    /// Enemy health and damage in percent of their base values.
+    fn enemy_percent(&self) -> u128 {
+        match self {
+            Difficulty::Easy => 75,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 150,
+        }
+    }
+
+    /// Battle rewards in percent of the balance config values.
+    fn reward_percent(&self) -> u128 {
+        match self {
+            Difficulty::Easy => 75,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 150,
+        }
+    }
+
+    pub fn scale_enemy(&self, value: u128) -> u128 {
+        cap(capped_mul(value, self.enemy_percent()) / 100)
+    }
+
+    pub fn scale_reward(&self, value: u128) -> u128 {
+        cap(capped_mul(value, self.reward_percent()) / 100)
+    }
+}
@@ -0,0 +1,77 @@
+use super::{battle::Entity, difficulty::Difficulty};
+
+/// How an enemy moves around the platformer arena.
+#[derive(Clone, Copy)]
+pub enum Behavior {
+    /// Walks back and forth on its platform, turning at walls and edges.
+    Patrol,
+    /// Runs straight at the player, jumping over walls.
+    Chase,
+    /// Chases the player and jumps across gaps between platforms.
+    Jumper,
+    /// Stays roughly this many cells away from the player.
+    KeepDistance(u16),
+}
+
+/// How an enemy hurts the player in the platformer arena.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AttackKind {
+    Melee,
+    Ranged,
+}
+
+pub struct EnemyTemplate {
+    pub name: &'static str,
+    pub symbol: char,
+    pub health: u128,
+    pub damage: u128,
+    pub behavior: Behavior,
+    pub attack: AttackKind,
+    /// Movement speed relative to the player.
+    pub speed: f64,
+}
+
+pub const ENEMY_TEMPLATES: [EnemyTemplate; 4] = [
+    EnemyTemplate {
+        name: "Slime",
+        symbol: 's',
+        health: 80,
+        damage: 8,
+        behavior: Behavior::Patrol,
+        attack: AttackKind::Melee,
+        speed: 0.3,
+    },
+    EnemyTemplate {
+        name: "Goblin",
+        symbol: 'g',
+        health: 100,
+        damage: 10,
+        behavior: Behavior::Chase,
+        attack: AttackKind::Melee,
+        speed: 0.5,
+    },
+    EnemyTemplate {
+        name: "Frog",
+        symbol: 'f',
+        health: 90,
+        damage: 9,
+        behavior: Behavior::Jumper,
+        attack: AttackKind::Melee,
+        speed: 0.6,
+    },
+    EnemyTemplate {
+        name: "Archer",
+        symbol: 'a',
+        health: 70,
+        damage: 12,
+        behavior: Behavior::KeepDistance(20),
+        attack: AttackKind::Ranged,
+        speed: 0.4,
+    },
+];
+
+impl EnemyTemplate {
+    pub fn to_entity(&self, difficulty: Difficulty) -> Entity {
+        Entity::new(self.health, self.damage, self.name, None).with_difficulty(difficulty)
+    }
+}
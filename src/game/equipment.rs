@@ -1,9 +1,88 @@
 use serde::{Deserialize, Serialize};
 
-use super::item::Item;
+use super::{
+    item::{Item, ItemProperties, ItemType},
+    item_sets::{find_set, ItemSet, SetBonus},
+    numeric::capped_add,
+};
 
 #[derive(Default, Serialize, Deserialize, Clone)]
 pub struct Equipment {
     pub sword: Option<Item>,
     pub shield: Option<Item>,
 }
+
+impl Equipment {
+    /// Puts the item in its slot, consumables can't be equipped and are ignored.
+    pub fn equip(&mut self, item: Item) {
+        match item.item_type {
+            ItemType::Sword => self.sword = Some(item),
+            ItemType::Shield => self.shield = Some(item),
+            ItemType::Consumable(_) => (),
+        }
+    }
+
+    pub fn repair_cost(&self) -> u128 {
+        self.items().map(|item| item.repair_cost()).sum()
+    }
+
+    pub fn repair(&mut self) {
+        for item in [&mut self.sword, &mut self.shield].into_iter().flatten() {
+            item.repair();
+        }
+    }
+
+    pub fn wear(&mut self, amount: u32) {
+        for item in [&mut self.sword, &mut self.shield].into_iter().flatten() {
+            item.durability = item.durability.saturating_sub(amount);
+        }
+    }
+
+    pub fn sanitize(&mut self) {
+        for item in [&mut self.sword, &mut self.shield].into_iter().flatten() {
+            item.sanitize();
+        }
+    }
+
+    fn items(&self) -> impl Iterator<Item = &Item> {
+        [&self.sword, &self.shield].into_iter().flatten()
+    }
+
+    /// Every set bonus unlocked by the currently worn pieces.
+    pub fn active_set_bonuses(&self) -> Vec<(&'static ItemSet, &'static SetBonus)> {
+        let mut counts: Vec<(&'static ItemSet, usize)> = vec![];
+        for set in self
+            .items()
+            .filter_map(|item| item.set.as_deref().and_then(find_set))
+        {
+            match counts.iter_mut().find(|(s, _)| s.name == set.name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((set, 1)),
+            }
+        }
+        counts
+            .into_iter()
+            .flat_map(|(set, count)| {
+                set.bonuses
+                    .iter()
+                    .filter(move |bonus| bonus.pieces <= count)
+                    .map(move |bonus| (set, bonus))
+            })
+            .collect()
+    }
+
+    /// Summed up health, damage and defence of all active set bonuses.
+    pub fn set_bonus_stats(&self) -> (u128, ItemProperties) {
+        let mut health = 0;
+        let mut properties = ItemProperties {
+            damage: 0,
+            defence: 0,
+        };
+        for (_, bonus) in self.active_set_bonuses() {
+            health = capped_add(health, bonus.health);
+            properties.damage = capped_add(properties.damage, bonus.damage);
+            properties.defence = capped_add(properties.defence, bonus.defence);
+        }
+        (health, properties)
+    }
+}
@@ -0,0 +1,30 @@
+use super::{
+    class::Class,
+    utils::{read_data_file, write_data_file},
+};
+use serde::{Deserialize, Serialize};
+
+const HALL_OF_FAME_FILE: &str = "hall_of_fame.json";
+
+/// Remembers a hardcore character that died.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Memorial {
+    pub name: String,
+    pub class: Class,
+    pub level: u128,
+    pub killed_by: String,
+}
+
+pub fn load_hall_of_fame() -> Vec<Memorial> {
+    read_data_file(HALL_OF_FAME_FILE)
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn add_memorial(memorial: Memorial) {
+    let mut hall = load_hall_of_fame();
+    hall.push(memorial);
+    if let Ok(content) = serde_json::to_string(&hall) {
+        write_data_file(HALL_OF_FAME_FILE, &content);
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::item::Item;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ItemStack {
+    pub item: Item,
+    pub amount: u32,
+}
+
+/// Backpack for items that aren't worn, identical items stack together.
+#[derive(Default, Serialize, Deserialize, Clone)]
+pub struct Inventory {
+    stacks: Vec<ItemStack>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, item: Item) {
+        match self.stacks.iter_mut().find(|s| s.item.name == item.name) {
+            Some(stack) => stack.amount = stack.amount.saturating_add(1),
+            None => self.stacks.push(ItemStack { item, amount: 1 }),
+        }
+    }
+
+    /// Takes a single item out of the stack at `index`.
+    pub fn take(&mut self, index: usize) -> Option<Item> {
+        let stack = self.stacks.get_mut(index)?;
+        let item = stack.item.clone();
+        stack.amount -= 1;
+        if stack.amount == 0 {
+            self.stacks.remove(index);
+        }
+        Some(item)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ItemStack> {
+        self.stacks.get(index)
+    }
+
+    pub fn stacks(&self) -> &[ItemStack] {
+        &self.stacks
+    }
+
+    pub fn sanitize(&mut self) {
+        self.stacks.retain(|stack| stack.amount > 0);
+        for stack in &mut self.stacks {
+            stack.item.sanitize();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+}
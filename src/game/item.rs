@@ -1,9 +1,27 @@
+use super::numeric::{cap, capped_add, capped_mul};
 use serde::{Deserialize, Serialize};
 
+const DEFAULT_DURABILITY: u32 = 100;
+
+fn default_durability() -> u32 {
+    DEFAULT_DURABILITY
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum ConsumableEffect {
+    /// Restores the given amount of health.
+    Heal(u128),
+    /// Adds the given amount of damage until the end of the battle.
+    Buff(u128),
+    /// Flees from the battle without any gains.
+    Escape,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub enum ItemType {
     Sword,
     Shield,
+    Consumable(ConsumableEffect),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -18,4 +36,214 @@ pub struct Item {
     pub item_type: ItemType,
     pub cost: u32,
     pub properties: ItemProperties,
+
+    #[serde(default = "default_durability")]
+    pub durability: u32,
+    #[serde(default = "default_durability")]
+    pub max_durability: u32,
+    #[serde(default)]
+    pub upgrade_level: u32,
+    #[serde(default)]
+    pub set: Option<String>,
+}
+
+impl Item {
+    pub fn new(name: &str, item_type: ItemType, cost: u32, properties: ItemProperties) -> Item {
+        Item {
+            name: name.into(),
+            item_type,
+            cost,
+            properties,
+            durability: DEFAULT_DURABILITY,
+            max_durability: DEFAULT_DURABILITY,
+            upgrade_level: 0,
+            set: None,
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        if self.upgrade_level == 0 {
+            self.name.clone()
+        } else {
+            format!("{} +{}", self.name, self.upgrade_level)
+        }
+    }
+
+    pub fn consumable(name: &str, cost: u32, effect: ConsumableEffect) -> Item {
+        Item::new(
+            name,
+            ItemType::Consumable(effect),
+            cost,
+            ItemProperties {
+                damage: 0,
+                defence: 0,
+            },
+        )
+    }
+
+    pub fn is_consumable(&self) -> bool {
+        matches!(self.item_type, ItemType::Consumable(_))
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.durability == 0
+    }
+
+    pub fn wear(&mut self) {
+        self.durability = self.durability.saturating_sub(1);
+    }
+
+    /// Properties the item actually provides, broken gear only gives half.
+    pub fn effective_properties(&self) -> ItemProperties {
+        if self.is_broken() {
+            ItemProperties {
+                damage: self.properties.damage / 2,
+                defence: self.properties.defence / 2,
+            }
+        } else {
+            self.properties.clone()
+        }
+    }
+
+    /// Coins needed to fully repair the item, proportional to missing durability.
+    pub fn repair_cost(&self) -> u128 {
+        if self.max_durability == 0 {
+            return 0;
+        }
+        let missing = self.max_durability.saturating_sub(self.durability) as u128;
+        let max = self.max_durability as u128;
+        (missing * self.cost as u128).div_ceil(max)
+    }
+
+    pub fn repair(&mut self) {
+        self.durability = self.max_durability;
+    }
+
+    pub fn upgrade_cost(&self) -> u128 {
+        let next_level = self.upgrade_level as u128 + 1;
+        capped_mul(self.cost as u128 + 10, capped_mul(next_level, next_level))
+    }
+
+    /// Chance in percent that the next upgrade succeeds.
+    pub fn upgrade_chance(&self) -> u32 {
+        100u32
+            .saturating_sub(self.upgrade_level.saturating_mul(15))
+            .max(10)
+    }
+
+    pub fn upgrade(&mut self) {
+        self.upgrade_level = self.upgrade_level.saturating_add(1);
+        let damage = self.properties.damage;
+        if damage > 0 {
+            self.properties.damage = capped_add(damage, (damage / 10).max(1));
+        }
+        let defence = self.properties.defence;
+        if defence > 0 {
+            self.properties.defence = capped_add(defence, (defence / 10).max(1));
+        }
+    }
+
+    /// Clamps values that could only come from a tampered save.
+    pub fn sanitize(&mut self) {
+        self.properties.damage = cap(self.properties.damage);
+        self.properties.defence = cap(self.properties.defence);
+        self.durability = self.durability.min(self.max_durability);
+        if let ItemType::Consumable(effect) = &mut self.item_type {
+            match effect {
+                ConsumableEffect::Heal(amount) | ConsumableEffect::Buff(amount) => {
+                    *amount = cap(*amount)
+                }
+                ConsumableEffect::Escape => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::numeric::MAX_VALUE;
+    use proptest::prelude::*;
+
+    fn sword() -> Item {
+        Item::new(
+            "Sword",
+            ItemType::Sword,
+            30,
+            ItemProperties {
+                damage: 10,
+                defence: 4,
+            },
+        )
+    }
+
+    #[test]
+    fn wear_stops_at_broken() {
+        let mut item = sword();
+        for _ in 0..DEFAULT_DURABILITY {
+            assert!(!item.is_broken());
+            item.wear();
+        }
+        assert!(item.is_broken());
+        item.wear();
+        assert_eq!(item.durability, 0);
+    }
+
+    #[test]
+    fn broken_items_give_half_properties() {
+        let mut item = sword();
+        assert_eq!(item.effective_properties().damage, 10);
+        assert_eq!(item.effective_properties().defence, 4);
+        item.durability = 0;
+        assert_eq!(item.effective_properties().damage, 5);
+        assert_eq!(item.effective_properties().defence, 2);
+    }
+
+    #[test]
+    fn repair_cost_follows_missing_durability() {
+        let mut item = sword();
+        assert_eq!(item.repair_cost(), 0);
+        item.durability = 50;
+        assert_eq!(item.repair_cost(), 15);
+        item.durability = 99;
+        assert_eq!(item.repair_cost(), 1);
+        item.durability = 0;
+        assert_eq!(item.repair_cost(), 30);
+        item.repair();
+        assert_eq!(item.repair_cost(), 0);
+        item.max_durability = 0;
+        assert_eq!(item.repair_cost(), 0);
+    }
+
+    #[test]
+    fn upgrades_get_pricier_and_riskier() {
+        let mut item = sword();
+        assert_eq!(item.upgrade_chance(), 100);
+        assert_eq!(item.upgrade_cost(), 40);
+        let mut last_cost = 0;
+        for _ in 0..20 {
+            assert!(item.upgrade_cost() > last_cost);
+            last_cost = item.upgrade_cost();
+            item.upgrade();
+        }
+        assert_eq!(item.upgrade_chance(), 10);
+        item.upgrade_level = u32::MAX;
+        assert_eq!(item.upgrade_chance(), 10);
+        assert!(item.upgrade_cost() <= MAX_VALUE);
+    }
+
+    proptest! {
+        #[test]
+        fn repair_cost_never_exceeds_item_cost(
+            cost: u32,
+            durability: u32,
+            max_durability: u32,
+        ) {
+            let mut item = sword();
+            item.cost = cost;
+            item.durability = durability;
+            item.max_durability = max_durability;
+            prop_assert!(item.repair_cost() <= cost as u128);
+        }
+    }
 }
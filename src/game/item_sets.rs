@@ -0,0 +1,37 @@
+pub struct SetBonus {
+    /// Amount of equipped pieces from the set needed for the bonus.
+    pub pieces: usize,
+    pub health: u128,
+    pub damage: u128,
+    pub defence: u128,
+}
+
+pub struct ItemSet {
+    pub name: &'static str,
+    pub bonuses: &'static [SetBonus],
+}
+
+pub const ITEM_SETS: &[ItemSet] = &[
+    ItemSet {
+        name: "Ironclad",
+        bonuses: &[SetBonus {
+            pieces: 2,
+            health: 40,
+            damage: 2,
+            defence: 3,
+        }],
+    },
+    ItemSet {
+        name: "Crystal",
+        bonuses: &[SetBonus {
+            pieces: 2,
+            health: 20,
+            damage: 8,
+            defence: 2,
+        }],
+    },
+];
+
+pub fn find_set(name: &str) -> Option<&'static ItemSet> {
+    ITEM_SETS.iter().find(|set| set.name == name)
+}
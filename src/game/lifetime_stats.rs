@@ -0,0 +1,64 @@
+use super::numeric::capped_add;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Counters that only ever grow over the life of a character.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct LifetimeStats {
+    #[serde(default)]
+    pub battles_won: u128,
+    #[serde(default)]
+    pub battles_lost: u128,
+    #[serde(default)]
+    pub damage_dealt: u128,
+    #[serde(default)]
+    pub coins_earned: u128,
+    #[serde(default)]
+    pub coins_spent: u128,
+    #[serde(default)]
+    pub items_bought: u128,
+    #[serde(default)]
+    play_time_ms: u128,
+}
+
+impl LifetimeStats {
+    pub fn battles_fought(&self) -> u128 {
+        capped_add(self.battles_won, self.battles_lost)
+    }
+
+    pub fn record_battle(&mut self, won: bool, damage_dealt: u128) {
+        if won {
+            self.battles_won = capped_add(self.battles_won, 1);
+        } else {
+            self.battles_lost = capped_add(self.battles_lost, 1);
+        }
+        self.damage_dealt = capped_add(self.damage_dealt, damage_dealt);
+    }
+
+    pub fn add_play_time(&mut self, elapsed: Duration) {
+        self.play_time_ms = capped_add(self.play_time_ms, elapsed.as_millis());
+    }
+
+    /// Play time formatted as hours, minutes and seconds.
+    pub fn play_time(&self) -> String {
+        let seconds = self.play_time_ms / 1000;
+        format!(
+            "{}h {:02}m {:02}s",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_time_is_formatted() {
+        let mut stats = LifetimeStats::default();
+        stats.add_play_time(Duration::from_millis(3_723_900));
+        assert_eq!(stats.play_time(), "1h 02m 03s");
+    }
+}
@@ -22,7 +22,7 @@ impl MessageQueue {
     pub fn get_message(&self) -> Option<String> {
         if let Some(queue) = &self.msgs {
             let unwrapped = queue.lock().unwrap();
-            let output = unwrapped.get(0);
+            let output = unwrapped.first();
             return output.cloned();
         }
         None
@@ -46,7 +46,7 @@ impl MessageQueue {
     pub fn pop_message(&mut self) {
         if let Some(queue) = &self.msgs {
             let mut unwrapped = queue.lock().unwrap();
-            if unwrapped.len() > 0 {
+            if !unwrapped.is_empty() {
                 unwrapped.remove(0);
             }
         }
@@ -0,0 +1,43 @@
+//! Stats, coins and xp are all capped at [`MAX_VALUE`] and only ever changed
+//! through saturating operations, so neither tampered saves nor very long
+//! sessions can overflow. A product of two capped values still fits in `u128`.
+
+pub const MAX_VALUE: u128 = 1_000_000_000_000_000_000;
+
+pub fn cap(value: u128) -> u128 {
+    value.min(MAX_VALUE)
+}
+
+pub fn capped_add(value: u128, amount: u128) -> u128 {
+    cap(value.saturating_add(amount))
+}
+
+pub fn capped_mul(value: u128, factor: u128) -> u128 {
+    cap(value.saturating_mul(factor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn capped_add_never_exceeds_cap(a: u128, b: u128) {
+            let sum = capped_add(a, b);
+            prop_assert!(sum <= MAX_VALUE);
+            prop_assert!(sum >= cap(a));
+            prop_assert!(sum >= cap(b));
+        }
+
+        #[test]
+        fn capped_add_is_exact_below_cap(a in 0..MAX_VALUE / 2, b in 0..MAX_VALUE / 2) {
+            prop_assert_eq!(capped_add(a, b), a + b);
+        }
+
+        #[test]
+        fn capped_mul_never_exceeds_cap(a: u128, b: u128) {
+            prop_assert!(capped_mul(a, b) <= MAX_VALUE);
+        }
+    }
+}
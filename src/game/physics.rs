@@ -0,0 +1,32 @@
+//! Movement constants of the platformer battle. Everything is in cells and
+//! seconds, so movement stays the same whatever the tick rate is.
+
+use super::arena_generator::JumpReach;
+
+pub const GRAVITY: f64 = 144.0;
+pub const WALK_ACCELERATION: f64 = 360.0;
+/// Walking accelerates this many times faster on the ground than in the air.
+pub const GROUND_GRIP: f64 = 6.0;
+pub const JUMP_SPEED: f64 = 24.0;
+/// Extra lift while jump is held in the air.
+pub const JUMP_HOLD_ACCELERATION: f64 = 72.0;
+pub const MAX_SPEED: f64 = 60.0;
+pub const MAX_FALL_SPEED: f64 = 120.0;
+/// Exponential decay rates of horizontal speed, per second.
+pub const AIR_DRAG: f64 = 6.3;
+pub const GROUND_FRICTION: f64 = 180.0;
+
+/// How far a jump gets with the constants above, minus a cell of leeway on
+/// each axis. Takes the walking speed balanced against ground friction,
+/// which is slower than anyone moves in the air, so the estimate errs on
+/// the short side.
+pub fn jump_reach() -> JumpReach {
+    let rise_deceleration = GRAVITY - JUMP_HOLD_ACCELERATION;
+    let height = JUMP_SPEED * JUMP_SPEED / (2.0 * rise_deceleration);
+    let air_time = JUMP_SPEED / rise_deceleration + (2.0 * height / GRAVITY).sqrt();
+    let walk_speed = WALK_ACCELERATION * GROUND_GRIP / GROUND_FRICTION;
+    JumpReach {
+        up: height as usize - 1,
+        across: (walk_speed * air_time) as usize - 1,
+    }
+}
@@ -1,5 +1,23 @@
-use super::{battle::Entity, equipment::Equipment, message_queue::MessageQueue};
+use super::{
+    achievements::{Achievements, GameEvent},
+    attributes::Attributes,
+    balance::balance,
+    battle::Entity,
+    class::{Class, SkillEffect},
+    difficulty::Difficulty,
+    equipment::Equipment,
+    inventory::Inventory,
+    item::{ConsumableEffect, Item, ItemType},
+    lifetime_stats::LifetimeStats,
+    message_queue::MessageQueue,
+    numeric::{cap, capped_add, capped_mul},
+    skill_tree::{find_node, ActiveSkill, SkillKind, SKILL_TREE},
+};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub const MAX_NAME_LENGTH: usize = 16;
 
 #[derive(Serialize, Deserialize)]
 pub struct Player {
@@ -11,6 +29,41 @@ pub struct Player {
     coins: u128,
     name: String,
     equipment: Equipment,
+    #[serde(default)]
+    materials: BTreeMap<String, u128>,
+    #[serde(default)]
+    inventory: Inventory,
+    #[serde(default)]
+    damage_buff: u128,
+    #[serde(default)]
+    attributes: Attributes,
+    #[serde(default)]
+    stat_points: u128,
+    #[serde(default)]
+    class: Class,
+    #[serde(default)]
+    skill_points: u128,
+    #[serde(default)]
+    unlocked_skills: BTreeSet<String>,
+    /// Health lost since the last full heal, so old saves start healthy.
+    #[serde(default)]
+    damage_taken: u128,
+    #[serde(default)]
+    hardcore: bool,
+    #[serde(default)]
+    difficulty: Difficulty,
+    /// Seed of the run, every battle gets its own seed derived from it.
+    #[serde(default)]
+    seed: u64,
+    #[serde(default)]
+    battles_seeded: u64,
+    #[serde(default)]
+    achievements: Achievements,
+    #[serde(default)]
+    lifetime_stats: LifetimeStats,
+
+    #[serde(skip_serializing, skip_deserializing)]
+    save_slot: u8,
 
     #[serde(skip_serializing, skip_deserializing)]
     msg_queue: MessageQueue,
@@ -30,11 +83,105 @@ impl Player {
     }
 
     pub fn get_health(&self) -> u128 {
-        self.base_health
+        capped_add(self.base_health, self.attributes.bonus_health())
     }
 
     pub fn get_damage(&self) -> u128 {
-        self.base_damage
+        capped_add(self.base_damage, self.attributes.bonus_damage())
+    }
+
+    pub fn get_class(&self) -> Class {
+        self.class
+    }
+
+    /// Picks the class for a fresh character, resetting its stats and gear.
+    pub fn choose_class(&mut self, class: Class) {
+        self.class = class;
+        (self.base_health, self.base_damage) = class.stats_from_level(self.level);
+        self.equipment = Equipment::default();
+        for item in class.starting_gear() {
+            self.receive_item(item);
+        }
+    }
+
+    /// Effects of every class skill unlocked at the current level and
+    /// every passive unlocked in the skill tree.
+    pub fn skill_effects(&self) -> Vec<SkillEffect> {
+        let class_skills = self
+            .class
+            .skills()
+            .iter()
+            .filter(|skill| skill.unlock_level <= self.level)
+            .map(|skill| skill.effect);
+        let passives = SKILL_TREE
+            .iter()
+            .filter(|node| self.has_skill(node.id))
+            .filter_map(|node| match node.kind {
+                SkillKind::Passive(effect) => Some(effect),
+                SkillKind::Active(_) => None,
+            });
+        class_skills.chain(passives).collect()
+    }
+
+    pub fn get_skill_points(&self) -> u128 {
+        self.skill_points
+    }
+
+    pub fn has_skill(&self, id: &str) -> bool {
+        self.unlocked_skills.contains(id)
+    }
+
+    pub fn can_unlock_skill(&self, id: &str) -> Result<(), &'static str> {
+        let Some(node) = find_node(id) else {
+            return Err("Unknown skill");
+        };
+        if self.has_skill(id) {
+            return Err("Skill is already unlocked");
+        }
+        if !node.prerequisites.iter().all(|req| self.has_skill(req)) {
+            return Err("Unlock the previous skills first");
+        }
+        if self.skill_points < node.cost {
+            return Err("Not enough skill points");
+        }
+        Ok(())
+    }
+
+    pub fn unlock_skill(&mut self, id: &str) -> Result<(), &'static str> {
+        self.can_unlock_skill(id)?;
+        if let Some(node) = find_node(id) {
+            self.skill_points -= node.cost;
+            self.unlocked_skills.insert(node.id.into());
+        }
+        Ok(())
+    }
+
+    pub fn active_skills(&self) -> Vec<ActiveSkill> {
+        SKILL_TREE
+            .iter()
+            .filter(|node| self.has_skill(node.id))
+            .filter_map(|node| match node.kind {
+                SkillKind::Active(skill) => Some(skill),
+                SkillKind::Passive(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn get_stat_points(&self) -> u128 {
+        self.stat_points
+    }
+
+    /// Spends stat points on the given attributes, does nothing if there aren't enough points.
+    pub fn allocate_attributes(&mut self, allocation: &Attributes) {
+        if allocation.total() > self.stat_points {
+            return;
+        }
+        self.stat_points -= allocation.total();
+        self.attributes.add(allocation);
     }
 
     pub fn get_xp(&self) -> u128 {
@@ -46,15 +193,17 @@ impl Player {
     }
 
     fn calculate_needed_xp(level: u128) -> u128 {
-        level.pow(2) * 40 + 60
+        balance().xp_curve.at(level)
     }
 
-    fn stats_from_level(level: u128) -> (u128, u128) {
-        (level * 30 + 100, level * 2 + 10)
+    pub fn is_max_level(&self) -> bool {
+        self.level >= balance().level_cap
     }
 
     pub fn add_coins(&mut self, coins: u128) {
-        self.coins += coins;
+        self.coins = capped_add(self.coins, coins);
+        self.lifetime_stats.coins_earned = capped_add(self.lifetime_stats.coins_earned, coins);
+        self.record_event(GameEvent::CoinsChanged(self.coins));
     }
 
     pub fn remove_coins(&mut self, remove_amount: u128) {
@@ -63,24 +212,69 @@ impl Player {
         }
     }
 
+    /// Removes coins paid for something, counting them as spent.
+    pub fn spend_coins(&mut self, amount: u128) {
+        if amount <= self.coins {
+            self.remove_coins(amount);
+            self.lifetime_stats.coins_spent = capped_add(self.lifetime_stats.coins_spent, amount);
+        }
+    }
+
     pub fn add_xp(&mut self, xp: u128) {
-        self.xp += xp;
+        if self.is_max_level() {
+            return;
+        }
+        self.xp = capped_add(self.xp, xp);
         let prev_level = self.level;
         while self.xp >= self.needed_xp {
             self.level += 1;
             self.xp -= self.needed_xp;
-            (self.base_health, self.base_damage) = Player::stats_from_level(self.level);
+            self.stat_points = capped_add(self.stat_points, balance().stat_points_per_level);
+            self.skill_points = capped_add(self.skill_points, balance().skill_points_per_level);
+            (self.base_health, self.base_damage) = self.class.stats_from_level(self.level);
             self.needed_xp = Player::calculate_needed_xp(self.level);
+            if self.is_max_level() {
+                self.xp = 0;
+                break;
+            }
         }
         if self.level != prev_level {
             if let Some(q) = &mut self.msg_queue.unwrap_queue() {
                 q.push("Level up!".into());
             }
+            self.record_event(GameEvent::LevelReached(self.level));
         }
     }
 
+    /// Brings a freshly loaded save back within the numeric caps and
+    /// recomputes everything that is derived from the level.
+    pub fn sanitize(&mut self) {
+        self.level = self.level.clamp(1, balance().level_cap);
+        (self.base_health, self.base_damage) = self.class.stats_from_level(self.level);
+        self.needed_xp = Player::calculate_needed_xp(self.level);
+        self.xp = if self.is_max_level() {
+            0
+        } else {
+            self.xp.min(self.needed_xp - 1)
+        };
+        self.coins = cap(self.coins);
+        self.stat_points = cap(self.stat_points);
+        self.skill_points = cap(self.skill_points);
+        self.damage_buff = cap(self.damage_buff);
+        self.attributes.sanitize();
+        self.equipment.sanitize();
+        self.inventory.sanitize();
+        self.materials.retain(|_, amount| *amount > 0);
+        for amount in self.materials.values_mut() {
+            *amount = cap(*amount);
+        }
+        self.damage_taken = self
+            .damage_taken
+            .min(self.get_max_health().saturating_sub(1));
+    }
+
     pub fn default() -> Player {
-        let (health, damage) = Player::stats_from_level(1);
+        let (health, damage) = Class::default().stats_from_level(1);
         Player {
             level: 1,
             base_health: health,
@@ -90,11 +284,48 @@ impl Player {
             coins: 0,
             needed_xp: Player::calculate_needed_xp(1),
             equipment: Equipment::default(),
+            materials: BTreeMap::new(),
+            inventory: Inventory::default(),
+            damage_buff: 0,
+            attributes: Attributes::default(),
+            stat_points: 0,
+            class: Class::default(),
+            skill_points: 0,
+            unlocked_skills: BTreeSet::new(),
+            damage_taken: 0,
+            hardcore: false,
+            difficulty: Difficulty::default(),
+            seed: 0,
+            battles_seeded: 0,
+            achievements: Achievements::default(),
+            lifetime_stats: LifetimeStats::default(),
+
+            save_slot: 1,
 
             msg_queue: MessageQueue::default(),
         }
     }
 
+    pub fn get_lifetime_stats(&self) -> &LifetimeStats {
+        &self.lifetime_stats
+    }
+
+    pub fn get_mut_lifetime_stats(&mut self) -> &mut LifetimeStats {
+        &mut self.lifetime_stats
+    }
+
+    pub fn get_achievements(&self) -> &Achievements {
+        &self.achievements
+    }
+
+    /// Feeds an event to the achievements and announces any that unlock.
+    pub fn record_event(&mut self, event: GameEvent) {
+        for achievement in self.achievements.record(event) {
+            self.msg_queue
+                .add_message(format!("Achievement unlocked: {}!", achievement.name));
+        }
+    }
+
     pub fn get_message_queue(&self) -> MessageQueue {
         self.msg_queue.clone()
     }
@@ -115,12 +346,464 @@ impl Player {
         &mut self.equipment
     }
 
+    pub fn get_inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    pub fn get_mut_inventory(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    /// Consumables go to the inventory, everything else is equipped right away.
+    pub fn receive_item(&mut self, item: Item) {
+        if item.is_consumable() {
+            self.inventory.add(item);
+        } else {
+            self.equipment.equip(item);
+        }
+    }
+
+    /// Uses a consumable from the inventory outside of a battle.
+    pub fn use_consumable(&mut self, index: usize) {
+        let Some(stack) = self.inventory.get(index) else {
+            return;
+        };
+        let msg = match &stack.item.item_type {
+            ItemType::Consumable(ConsumableEffect::Buff(amount)) => {
+                let amount = *amount;
+                self.inventory.take(index);
+                self.damage_buff = capped_add(self.damage_buff, amount);
+                format!("You feel stronger! +{} damage in the next battle", amount)
+            }
+            ItemType::Consumable(ConsumableEffect::Heal(amount)) => {
+                if self.damage_taken == 0 {
+                    "You are already at full health".into()
+                } else {
+                    let amount = *amount;
+                    self.inventory.take(index);
+                    self.heal(amount);
+                    format!("You feel better! Health: {}", self.get_current_health())
+                }
+            }
+            _ => "This item can only be used in a battle".into(),
+        };
+        self.msg_queue.add_message(msg);
+    }
+
+    pub fn clear_buffs(&mut self) {
+        self.damage_buff = 0;
+    }
+
+    pub fn get_materials(&self) -> &BTreeMap<String, u128> {
+        &self.materials
+    }
+
+    pub fn add_material(&mut self, name: &str, amount: u128) {
+        let owned = self.materials.entry(name.into()).or_insert(0);
+        *owned = capped_add(*owned, amount);
+    }
+
+    pub fn has_materials(&self, materials: &[(String, u128)]) -> bool {
+        materials
+            .iter()
+            .all(|(name, amount)| self.materials.get(name).copied().unwrap_or(0) >= *amount)
+    }
+
+    /// Removes all given materials at once, or nothing if some are missing.
+    pub fn remove_materials(&mut self, materials: &[(String, u128)]) -> bool {
+        if !self.has_materials(materials) {
+            return false;
+        }
+        for (name, amount) in materials {
+            if let Some(owned) = self.materials.get_mut(name) {
+                *owned -= amount;
+                if *owned == 0 {
+                    self.materials.remove(name);
+                }
+            }
+        }
+        true
+    }
+
+    /// Health including equipment set bonuses and skills.
+    pub fn get_max_health(&self) -> u128 {
+        let mut health = capped_add(self.get_health(), self.equipment.set_bonus_stats().0);
+        for effect in self.skill_effects() {
+            if let SkillEffect::BonusHealth(amount) = effect {
+                health = capped_add(health, amount);
+            }
+        }
+        health
+    }
+
+    pub fn get_current_health(&self) -> u128 {
+        self.get_max_health().saturating_sub(self.damage_taken)
+    }
+
+    /// Updates current health after a fight, a knocked out player keeps 1 hp.
+    pub fn set_current_health(&mut self, health: u128) {
+        self.damage_taken = self.get_max_health().saturating_sub(health.max(1));
+    }
+
+    pub fn heal(&mut self, amount: u128) {
+        self.damage_taken = self.damage_taken.saturating_sub(amount);
+    }
+
+    fn is_name_char(ch: char) -> bool {
+        ch.is_alphanumeric() || ch == ' ' || ch == '-' || ch == '_'
+    }
+
+    /// A name character, letters and digits can carry combining marks so
+    /// decomposed accents work the same as precomposed ones.
+    pub fn is_name_grapheme(grapheme: &str) -> bool {
+        let mut chars = grapheme.chars();
+        let Some(base) = chars.next() else {
+            return false;
+        };
+        let marks = chars.as_str();
+        Player::is_name_char(base) && (marks.is_empty() || base.is_alphanumeric())
+    }
+
+    /// Checks a nickname: 1 to 16 letters, digits, spaces, `-` or `_`.
+    pub fn validate_name(name: &str) -> Result<(), &'static str> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Name can't be empty");
+        }
+        // Counted like the text input counts, so whatever fits in the prompt passes.
+        if name.graphemes(true).count() > MAX_NAME_LENGTH {
+            return Err("Name can't be longer than 16 characters");
+        }
+        if !name.graphemes(true).all(Player::is_name_grapheme) {
+            return Err("Name can only contain letters, digits, spaces, - and _");
+        }
+        Ok(())
+    }
+
+    pub fn get_save_slot(&self) -> u8 {
+        self.save_slot
+    }
+
+    pub fn set_save_slot(&mut self, slot: u8) {
+        self.save_slot = slot;
+    }
+
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.battles_seeded = 0;
+    }
+
+    /// Seed for the next battle, the same run seed always gives the same battles.
+    pub fn next_battle_seed(&mut self) -> u64 {
+        self.battles_seeded = self.battles_seeded.wrapping_add(1);
+        self.seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(self.battles_seeded.wrapping_mul(1442695040888963407))
+    }
+
+    pub fn is_hardcore(&self) -> bool {
+        self.hardcore
+    }
+
+    pub fn set_hardcore(&mut self, hardcore: bool) {
+        self.hardcore = hardcore;
+    }
+
+    /// Takes coins, level progress and durability after a lost battle.
+    /// Returns the amount of coins and xp lost.
+    pub fn apply_defeat_penalty(&mut self) -> (u128, u128) {
+        let penalty = balance().defeat_penalty;
+        let coins_lost = capped_mul(self.coins, penalty.coin_loss_percent) / 100;
+        let xp_lost = capped_mul(self.xp, penalty.xp_loss_percent) / 100;
+        self.remove_coins(coins_lost);
+        self.xp -= xp_lost;
+        self.equipment.wear(penalty.durability_loss);
+        (coins_lost, xp_lost)
+    }
+
+    pub fn rest_cost(&self) -> u128 {
+        capped_mul(self.level, balance().rest_cost_per_level)
+    }
+
+    /// Pays for a night at the inn, fully restoring health.
+    pub fn rest(&mut self) {
+        let msg = if self.damage_taken == 0 {
+            "You are already at full health".into()
+        } else if self.coins < self.rest_cost() {
+            format!("Not enough coins to rest ({}c)", self.rest_cost())
+        } else {
+            self.spend_coins(self.rest_cost());
+            self.damage_taken = 0;
+            "You had a good rest and feel fully healed".into()
+        };
+        self.msg_queue.add_message(msg);
+    }
+
     pub fn to_entity(&self) -> Entity {
-        Entity::new(
-            self.base_health,
-            self.base_damage,
-            &self.name,
-            Some(self.equipment.clone()),
-        )
+        let properties = self.equipment.set_bonus_stats().1;
+        let health = self.get_max_health();
+        let mut damage = capped_add(
+            capped_add(self.get_damage(), self.damage_buff),
+            properties.damage,
+        );
+        let mut defence = 0;
+        let mut dodge_chance = self.attributes.dodge_chance();
+        let mut crit_chance = self.attributes.crit_chance();
+        for effect in self.skill_effects() {
+            match effect {
+                SkillEffect::BonusHealth(_) => (),
+                SkillEffect::BonusDamage(amount) => damage = capped_add(damage, amount),
+                SkillEffect::BonusDefence(amount) => defence = capped_add(defence, amount),
+                SkillEffect::CritChance(chance) => crit_chance += chance,
+                SkillEffect::DodgeChance(chance) => dodge_chance += chance,
+            }
+        }
+        Entity::new(health, damage, &self.name, Some(self.equipment.clone()))
+            .with_current_health(self.get_current_health())
+            .with_chances(dodge_chance, crit_chance)
+            .with_defence(defence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{item::ItemProperties, numeric::MAX_VALUE};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn gains_stay_within_caps(gains in proptest::collection::vec((any::<u128>(), any::<u128>()), 0..20)) {
+            let mut player = Player::default();
+            for (xp, coins) in gains {
+                player.add_xp(xp);
+                player.add_coins(coins);
+                prop_assert!(player.get_coins() <= MAX_VALUE);
+                prop_assert!(player.get_level() <= balance().level_cap);
+                prop_assert!(player.is_max_level() || player.get_xp() < player.get_nxp());
+            }
+            let entity = player.to_entity();
+            prop_assert!(entity.get_health() <= MAX_VALUE);
+        }
+
+        #[test]
+        fn sanitize_fixes_tampered_saves(
+            level: u128,
+            xp: u128,
+            needed_xp: u128,
+            coins: u128,
+            strength: u128,
+        ) {
+            let mut player = Player::default();
+            player.level = level;
+            player.xp = xp;
+            player.needed_xp = needed_xp;
+            player.coins = coins;
+            player.attributes.strength = strength;
+            player.sanitize();
+
+            prop_assert!(player.get_level() >= 1 && player.get_level() <= balance().level_cap);
+            prop_assert!(player.get_nxp() > 0);
+            prop_assert!(player.is_max_level() || player.get_xp() < player.get_nxp());
+            prop_assert!(player.get_coins() <= MAX_VALUE);
+            prop_assert!(player.get_damage() <= MAX_VALUE);
+            player.add_xp(xp);
+        }
+    }
+
+    #[test]
+    fn level_ups_award_stat_points() {
+        let mut player = Player::default();
+        player.add_xp(player.get_nxp());
+        player.add_xp(player.get_nxp());
+        assert_eq!(player.get_level(), 3);
+        assert_eq!(
+            player.get_stat_points(),
+            2 * balance().stat_points_per_level
+        );
+    }
+
+    #[test]
+    fn allocation_needs_enough_points() {
+        let mut player = Player::default();
+        let allocation = Attributes {
+            strength: 1,
+            ..Attributes::default()
+        };
+        player.allocate_attributes(&allocation);
+        assert_eq!(player.get_attributes().strength, 0);
+        assert_eq!(player.get_damage(), Class::default().stats_from_level(1).1);
+
+        player.stat_points = 1;
+        player.allocate_attributes(&Attributes {
+            strength: 1,
+            vitality: 1,
+            ..Attributes::default()
+        });
+        assert_eq!(player.get_attributes().total(), 0);
+        assert_eq!(player.get_stat_points(), 1);
+
+        player.allocate_attributes(&allocation);
+        assert_eq!(player.get_attributes().strength, 1);
+        assert_eq!(player.get_stat_points(), 0);
+    }
+
+    #[test]
+    fn classes_grow_their_own_way() {
+        for class in Class::ALL {
+            let mut player = Player::default();
+            player.choose_class(class);
+            assert_eq!(player.get_health(), class.stats_from_level(1).0);
+            for _ in 0..5 {
+                player.add_xp(player.get_nxp());
+            }
+            let (health, damage) = class.stats_from_level(6);
+            assert_eq!(player.get_level(), 6);
+            assert_eq!(player.get_health(), health);
+            assert_eq!(player.get_damage(), damage);
+        }
+        let warrior = Class::Warrior.stats_from_level(10);
+        let mage = Class::Mage.stats_from_level(10);
+        assert!(warrior.0 > mage.0);
+        assert!(warrior.1 < mage.1);
+    }
+
+    #[test]
+    fn skills_need_prerequisites_and_points() {
+        let mut player = Player::default();
+        assert_eq!(
+            player.unlock_skill("toughness"),
+            Err("Not enough skill points")
+        );
+
+        player.skill_points = 10;
+        assert_eq!(
+            player.unlock_skill("iron_skin"),
+            Err("Unlock the previous skills first")
+        );
+        assert_eq!(player.get_skill_points(), 10);
+        assert!(!player.has_skill("iron_skin"));
+
+        assert_eq!(player.unlock_skill("toughness"), Ok(()));
+        assert_eq!(
+            player.unlock_skill("toughness"),
+            Err("Skill is already unlocked")
+        );
+        assert_eq!(player.unlock_skill("iron_skin"), Ok(()));
+        assert_eq!(player.get_skill_points(), 7);
+
+        player.skill_points = 2;
+        assert_eq!(
+            player.unlock_skill("second_wind"),
+            Err("Not enough skill points")
+        );
+        assert!(player.active_skills().is_empty());
+        assert_eq!(player.unlock_skill("missing"), Err("Unknown skill"));
+    }
+
+    #[test]
+    fn missing_materials_consume_nothing() {
+        let mut player = Player::default();
+        player.add_material("Wood", 4);
+        player.add_material("Iron Ore", 1);
+        let recipe = [("Wood".to_string(), 2), ("Iron Ore".to_string(), 3)];
+        assert!(!player.remove_materials(&recipe));
+        assert_eq!(player.get_materials().get("Wood"), Some(&4));
+        assert_eq!(player.get_materials().get("Iron Ore"), Some(&1));
+
+        player.add_material("Iron Ore", 2);
+        assert!(player.remove_materials(&recipe));
+        assert_eq!(player.get_materials().get("Wood"), Some(&2));
+        assert!(!player.get_materials().contains_key("Iron Ore"));
+    }
+
+    #[test]
+    fn resting_costs_coins_per_level_and_heals_to_max() {
+        let mut player = Player::default();
+        player.add_xp(player.get_nxp());
+        let cost = 2 * balance().rest_cost_per_level;
+        assert_eq!(player.rest_cost(), cost);
+
+        player.add_coins(cost);
+        player.rest();
+        assert_eq!(player.get_coins(), cost);
+
+        player.set_current_health(1);
+        player.remove_coins(1);
+        player.rest();
+        assert_eq!(player.get_current_health(), 1);
+        assert_eq!(player.get_coins(), cost - 1);
+
+        player.add_coins(1);
+        player.rest();
+        assert_eq!(player.get_current_health(), player.get_max_health());
+        assert_eq!(player.get_coins(), 0);
+        player.heal(u128::MAX);
+        assert_eq!(player.get_current_health(), player.get_max_health());
+    }
+
+    #[test]
+    fn defeat_takes_a_share_of_coins_xp_and_durability() {
+        let penalty = balance().defeat_penalty;
+        let mut player = Player::default();
+        player.coins = 1000;
+        player.xp = 40;
+        let gear = |item_type: ItemType, durability: u32| {
+            let properties = ItemProperties {
+                damage: 1,
+                defence: 1,
+            };
+            let mut item = Item::new("Gear", item_type, 10, properties);
+            item.durability = durability;
+            item
+        };
+        let worn = penalty.durability_loss.saturating_sub(1);
+        player.equipment.equip(gear(ItemType::Sword, worn));
+        player.equipment.equip(gear(ItemType::Shield, 100));
+
+        let coins_lost = 1000 * penalty.coin_loss_percent / 100;
+        let xp_lost = 40 * penalty.xp_loss_percent / 100;
+        assert_eq!(player.apply_defeat_penalty(), (coins_lost, xp_lost));
+        assert_eq!(player.get_coins(), 1000 - coins_lost);
+        assert_eq!(player.get_xp(), 40 - xp_lost);
+        let equipment = player.get_equipment();
+        assert_eq!(equipment.sword.as_ref().unwrap().durability, 0);
+        let shield = equipment.shield.as_ref().unwrap();
+        assert_eq!(
+            shield.durability,
+            100u32.saturating_sub(penalty.durability_loss)
+        );
+
+        player.coins = 0;
+        player.xp = 0;
+        assert_eq!(player.apply_defeat_penalty(), (0, 0));
+    }
+
+    #[test]
+    fn names_accept_combining_marks_on_letters() {
+        assert!(Player::validate_name("Rene\u{301}e").is_ok());
+        assert!(Player::validate_name("Ren\u{e9}e").is_ok());
+        assert!(!Player::is_name_grapheme("-\u{301}"));
+        assert!(Player::validate_name("a-\u{301}").is_err());
+    }
+
+    #[test]
+    fn name_length_counts_graphemes() {
+        let decomposed = "e\u{301}".repeat(MAX_NAME_LENGTH);
+        assert!(Player::validate_name(&decomposed).is_ok());
+        let too_long = "e\u{301}".repeat(MAX_NAME_LENGTH + 1);
+        assert!(Player::validate_name(&too_long).is_err());
     }
 }
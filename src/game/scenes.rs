@@ -1,17 +1,32 @@
 use self::{
-    battle::BattleScene, gains::GainsScene, new_battle::NBattleScene, shop::ShopScene,
-    stats::StatisticsScene, username::UsernameScene,
+    achievements::AchievementsScene, arena_select::ArenaSelectScene, attributes::AttributesScene,
+    battle::BattleScene, crafting::CraftingScene, gains::GainsScene, hall_of_fame::HallOfFameScene,
+    inventory::InventoryScene, new_battle::NBattleScene, new_game::NewGameScene, shop::ShopScene,
+    skill_tree::SkillTreeScene, stats::StatisticsScene, username::UsernameScene,
+};
+use super::{
+    balance::balance_error, message_queue::MessageQueue, player::Player, utils::render_border_type,
 };
-use super::{message_queue::MessageQueue, player::Player, utils::render_border_type};
 use crate::Frame;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{prelude::Rect, style::Stylize, widgets::Paragraph};
-use std::cmp::max;
+use std::{
+    cmp::max,
+    time::{Duration, Instant},
+};
 
+mod achievements;
+mod arena_select;
+mod attributes;
 mod battle;
+mod crafting;
 mod gains;
+mod hall_of_fame;
+mod inventory;
 mod new_battle;
+pub mod new_game;
 mod shop;
+mod skill_tree;
 pub mod stats;
 mod username;
 
@@ -35,6 +50,12 @@ impl SharedData {
     pub fn is_terminating(&self) -> bool {
         self.terminate
     }
+
+    pub fn add_play_time(&mut self, elapsed: Duration) {
+        self.player_data
+            .get_mut_lifetime_stats()
+            .add_play_time(elapsed);
+    }
 }
 
 pub trait Scene {
@@ -43,14 +64,72 @@ pub trait Scene {
 
     fn render(&self, frame: &mut Frame, data: &SharedData);
     fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData);
+    /// Text pasted into the terminal, only scenes with text prompts need it.
+    fn handle_paste(&mut self, _text: &str, _data: &mut SharedData) {}
     fn update(&mut self, data: &mut SharedData);
 }
 
+/// Scenes are simulated at a fixed rate, independent of how often frames
+/// are drawn or keys arrive.
+pub const TICKS_PER_SECOND: u32 = 60;
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / TICKS_PER_SECOND as u64);
+/// Upper bound on ticks simulated per frame, so the game skips time after a
+/// long stall instead of freezing while it catches up.
+const MAX_TICKS_PER_FRAME: u32 = 5;
+
 pub struct SceneManager {
     current_scene: Box<dyn Scene>,
     message_queue: MessageQueue,
     lifetime: u128,
     message_highlight_ticks: u8,
+    accumulator: Duration,
+    performance: PerformanceCounter,
+}
+
+/// Frames and ticks per second, shown with F3.
+struct PerformanceCounter {
+    visible: bool,
+    since: Instant,
+    frames: u32,
+    ticks: u32,
+    fps: u32,
+    tps: u32,
+}
+
+impl PerformanceCounter {
+    fn new() -> Self {
+        PerformanceCounter {
+            visible: false,
+            since: Instant::now(),
+            frames: 0,
+            ticks: 0,
+            fps: 0,
+            tps: 0,
+        }
+    }
+
+    fn frame(&mut self) {
+        self.frames += 1;
+        if self.since.elapsed() >= Duration::from_secs(1) {
+            self.fps = self.frames;
+            self.tps = self.ticks;
+            self.frames = 0;
+            self.ticks = 0;
+            self.since = Instant::now();
+        }
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let text = format!("FPS {} | TPS {}", self.fps, self.tps);
+        let width = (text.len() as u16).min(frame.size().width);
+        let area = Rect {
+            x: frame.size().width - width,
+            y: 0,
+            width,
+            height: 1,
+        };
+        frame.render_widget(Paragraph::new(text).on_dark_gray(), area);
+    }
 }
 impl SceneManager {
     pub fn new(scene: impl Scene + 'static) -> Self {
@@ -59,7 +138,12 @@ impl SceneManager {
             message_queue: MessageQueue::new(),
             lifetime: 0,
             message_highlight_ticks: 0,
+            accumulator: Duration::ZERO,
+            performance: PerformanceCounter::new(),
         };
+        if let Some(err) = balance_error() {
+            manager.message_queue.add_message(err.into());
+        }
         manager
             .current_scene
             .set_message_queue(manager.message_queue.clone());
@@ -70,10 +154,10 @@ impl SceneManager {
         self.message_queue.clone()
     }
 
-    fn render_message(&mut self, frame: &mut Frame, msg: &String) {
+    fn render_message(&mut self, frame: &mut Frame, msg: &str) {
         let altername_title = "Press X | Enter | Esc to close";
         let default_title = "Message";
-        let mut p = Paragraph::new(msg.clone()).bold();
+        let mut p = Paragraph::new(msg.to_string()).bold();
 
         let title = if self.message_highlight_ticks > 0 {
             self.message_highlight_ticks -= 1;
@@ -101,9 +185,20 @@ impl SceneManager {
         if let Some(msg) = self.message_queue.get_message() {
             self.render_message(frame, &msg)
         }
+
+        self.performance.frame();
+        if self.performance.visible {
+            self.performance.render(frame);
+        }
     }
 
     pub fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.code == KeyCode::F(3) {
+            if key.kind == KeyEventKind::Press {
+                self.performance.visible = !self.performance.visible;
+            }
+            return;
+        }
         if !self.message_queue.has_message() {
             let key_codes = [KeyCode::Enter, KeyCode::Char('x'), KeyCode::Esc];
             if key_codes.contains(&key.code) {
@@ -117,14 +212,41 @@ impl SceneManager {
         }
     }
 
-    pub fn update(&mut self, data: &mut SharedData) {
+    pub fn handle_paste(&mut self, text: &str, data: &mut SharedData) {
+        if self.message_queue.has_message() {
+            self.current_scene.handle_paste(text, data);
+        }
+    }
+
+    /// Runs as many fixed ticks as fit into the time since the last call.
+    pub fn advance(&mut self, elapsed: Duration, data: &mut SharedData) {
+        self.accumulator += elapsed;
+        let mut ticks = 0;
+        while self.accumulator >= TICK {
+            if ticks == MAX_TICKS_PER_FRAME {
+                self.accumulator = Duration::ZERO;
+                break;
+            }
+            self.accumulator -= TICK;
+            self.update(data);
+            ticks += 1;
+        }
+    }
+
+    /// How long the main loop can wait for input before the next tick is due.
+    pub fn time_until_tick(&self) -> Duration {
+        TICK.saturating_sub(self.accumulator)
+    }
+
+    fn update(&mut self, data: &mut SharedData) {
         self.lifetime += 1;
+        self.performance.ticks += 1;
         self.current_scene.update(data);
 
         if self.current_scene.scene_id() != data.current_scene {
             match data.current_scene {
                 _id if _id == UsernameScene::scene_id() => {
-                    self.current_scene = Box::new(UsernameScene::new())
+                    self.current_scene = Box::new(UsernameScene::new(data))
                 }
                 _id if _id == StatisticsScene::scene_id() => {
                     self.current_scene = Box::new(StatisticsScene::new())
@@ -138,6 +260,30 @@ impl SceneManager {
                 _id if _id == GainsScene::scene_id() => {
                     self.current_scene = Box::new(GainsScene::new(data))
                 }
+                _id if _id == CraftingScene::scene_id() => {
+                    self.current_scene = Box::new(CraftingScene::new())
+                }
+                _id if _id == InventoryScene::scene_id() => {
+                    self.current_scene = Box::new(InventoryScene::new())
+                }
+                _id if _id == AttributesScene::scene_id() => {
+                    self.current_scene = Box::new(AttributesScene::new())
+                }
+                _id if _id == NewGameScene::scene_id() => {
+                    self.current_scene = Box::new(NewGameScene::new())
+                }
+                _id if _id == SkillTreeScene::scene_id() => {
+                    self.current_scene = Box::new(SkillTreeScene::new())
+                }
+                _id if _id == HallOfFameScene::scene_id() => {
+                    self.current_scene = Box::new(HallOfFameScene::new())
+                }
+                _id if _id == AchievementsScene::scene_id() => {
+                    self.current_scene = Box::new(AchievementsScene::new())
+                }
+                _id if _id == ArenaSelectScene::scene_id() => {
+                    self.current_scene = Box::new(ArenaSelectScene::new())
+                }
                 _id if _id == NBattleScene::scene_id() => {
                     self.current_scene = Box::new(NBattleScene::new(data))
                 }
@@ -149,3 +295,53 @@ impl SceneManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE_SCENE_ID: i32 = -1;
+
+    struct IdleScene;
+
+    impl Scene for IdleScene {
+        fn scene_id(&self) -> i32 {
+            IDLE_SCENE_ID
+        }
+
+        fn set_message_queue(&mut self, _: MessageQueue) {}
+
+        fn render(&self, _: &mut Frame, _: &SharedData) {}
+
+        fn handle_input(&mut self, _: KeyEvent, _: &mut SharedData) {}
+
+        fn update(&mut self, _: &mut SharedData) {}
+    }
+
+    fn setup() -> (SceneManager, SharedData) {
+        let data = SharedData::new(Player::default(), IDLE_SCENE_ID);
+        (SceneManager::new(IdleScene), data)
+    }
+
+    #[test]
+    fn long_stalls_are_skipped() {
+        let (mut manager, mut data) = setup();
+        manager.advance(Duration::from_secs(10), &mut data);
+        assert_eq!(manager.lifetime, MAX_TICKS_PER_FRAME as u128);
+        assert_eq!(manager.accumulator, Duration::ZERO);
+        assert_eq!(manager.time_until_tick(), TICK);
+    }
+
+    #[test]
+    fn partial_ticks_carry_over() {
+        let (mut manager, mut data) = setup();
+        manager.advance(TICK * 3 / 2, &mut data);
+        assert_eq!(manager.lifetime, 1);
+        assert_eq!(manager.accumulator, TICK / 2);
+        manager.advance(TICK / 2, &mut data);
+        assert_eq!(manager.lifetime, 2);
+        assert_eq!(manager.accumulator, Duration::ZERO);
+        manager.advance(TICK - Duration::from_nanos(1), &mut data);
+        assert_eq!(manager.lifetime, 2);
+    }
+}
@@ -0,0 +1,99 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::{achievements::ACHIEVEMENTS, message_queue::MessageQueue, utils::calculate_bar};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 11;
+
+pub struct AchievementsScene {
+    message_queue: MessageQueue,
+}
+
+impl AchievementsScene {
+    pub fn new() -> Self {
+        AchievementsScene {
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+}
+
+impl Scene for AchievementsScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if let KeyCode::Enter | KeyCode::Esc = key.code {
+            data.current_scene = StatisticsScene::scene_id()
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
+        let achievements = data.player_data.get_achievements();
+        let unlocked = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| achievements.is_unlocked(achievement.id))
+            .count();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Achievements", Style::default().bold()),
+                Span::styled(
+                    format!(" {}/{}", unlocked, ACHIEVEMENTS.len()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        for achievement in &ACHIEVEMENTS {
+            let (value, goal) = achievements.progress(achievement.id);
+            let done = achievements.is_unlocked(achievement.id);
+            let name_style = if done {
+                Style::default().bold().fg(Color::Yellow)
+            } else {
+                Style::default().bold()
+            };
+            let (filled, missing) = calculate_bar(value, goal, 10);
+            lines.push(Line::from(vec![
+                Span::styled(achievement.name, name_style),
+                Span::styled(
+                    format!(" - {}", achievement.description),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(filled, Style::default().bold().fg(Color::Yellow)),
+                Span::styled(missing, Style::default().bold().fg(Color::DarkGray)),
+                Span::raw(format!("  {}/{}", value, goal)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Press Enter to go back..."));
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
@@ -0,0 +1,178 @@
+use super::{
+    new_battle::NBattleScene,
+    new_game::{parse_seed, MAX_SEED_LENGTH},
+    stats::StatisticsScene,
+    Scene, SharedData,
+};
+use crate::game::{
+    arena::{load_arenas, Arena},
+    message_queue::MessageQueue,
+    ui::text_input::TextInput,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serde::{Deserialize, Serialize};
+
+const SCENE_ID: i32 = 12;
+const MAX_WAVES: u32 = 5;
+
+/// Everything the platformer battle needs to know before it starts.
+#[derive(Serialize, Deserialize)]
+pub struct BattleSetup {
+    pub seed: u64,
+    /// Name of a handcrafted arena, `None` for a random one.
+    pub arena: Option<String>,
+    /// Each wave spawns once the previous one is cleared.
+    pub waves: u32,
+}
+
+pub struct ArenaSelectScene {
+    arenas: Vec<Arena>,
+    errors: Vec<String>,
+    /// 0 is the random arena, the rest index into `arenas` shifted by one.
+    selected_id: usize,
+    waves: u32,
+    /// Replays a fight with a seed shown in an earlier battle.
+    seed: TextInput,
+    message_queue: MessageQueue,
+}
+
+impl ArenaSelectScene {
+    pub fn new() -> Self {
+        let (arenas, errors) = load_arenas();
+        ArenaSelectScene {
+            arenas,
+            errors,
+            selected_id: 0,
+            waves: 1,
+            seed: TextInput::new(MAX_SEED_LENGTH)
+                .with_charset(|grapheme| grapheme.chars().all(|ch| ch.is_ascii_digit())),
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+
+    fn selected_arena(&self) -> Option<&Arena> {
+        self.selected_id
+            .checked_sub(1)
+            .and_then(|id| self.arenas.get(id))
+    }
+}
+
+impl Scene for ArenaSelectScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+        for error in self.errors.drain(..) {
+            self.message_queue
+                .add_message(format!("Couldn't load arena, {}", error));
+        }
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        match key.code {
+            KeyCode::Up if self.selected_id > 0 => self.selected_id -= 1,
+            KeyCode::Down if self.selected_id < self.arenas.len() => self.selected_id += 1,
+            KeyCode::Enter => {
+                let seed = match parse_seed(self.seed.value()) {
+                    Ok(Some(seed)) => seed,
+                    Ok(None) => data.player_data.next_battle_seed(),
+                    Err(err) => {
+                        self.message_queue.add_message(err);
+                        return;
+                    }
+                };
+                let setup = BattleSetup {
+                    seed,
+                    arena: self.selected_arena().map(|arena| arena.name.clone()),
+                    waves: self.waves,
+                };
+                data.scene_data_transfer = Some(serde_json::to_string(&setup).unwrap());
+                data.current_scene = NBattleScene::scene_id();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') if self.waves < MAX_WAVES => self.waves += 1,
+            KeyCode::Char('-') if self.waves > 1 => self.waves -= 1,
+            KeyCode::Esc => data.current_scene = StatisticsScene::scene_id(),
+            _ => {
+                self.seed.handle_key(&key);
+            }
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str, _: &mut SharedData) {
+        self.seed.insert_str(text);
+    }
+
+    fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
+        let mut lines = vec![Line::from("Choose an arena".bold()), Line::from("")];
+        let names = ["Random"]
+            .into_iter()
+            .chain(self.arenas.iter().map(|arena| arena.name.as_str()));
+        for (id, name) in names.enumerate() {
+            let style = if id == self.selected_id {
+                Style::default().bg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(name.to_string(), style)));
+        }
+        lines.push(Line::from(""));
+        match self.selected_arena() {
+            Some(arena) => {
+                let style = Style::default().fg(Color::DarkGray);
+                lines.push(Line::from(Span::styled(arena.description.clone(), style)));
+                lines.push(Line::from(Span::styled(
+                    format!("{}x{}", arena.width, arena.height),
+                    style,
+                )));
+            }
+            None => lines.push(Line::from(
+                "A new arena for every fight".fg(Color::DarkGray),
+            )),
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Waves "),
+            Span::styled(self.waves.to_string(), Style::default().bold()),
+            Span::styled(
+                " (+/- to change, later waves bring more enemies)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::raw("Seed "),
+            Span::styled(
+                "(type one to replay a battle, leave empty for a new one)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        let width = frame.size().width.saturating_sub(1) as usize;
+        lines.push(self.seed.line(width, Style::default()));
+        lines.push(Line::from(""));
+        lines.push(Line::from("Enter to fight, Esc to go back"));
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
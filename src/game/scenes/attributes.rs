@@ -0,0 +1,154 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::{attributes::Attributes, message_queue::MessageQueue};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 7;
+
+pub struct AttributesScene {
+    selected_id: usize,
+    pending: Attributes,
+    buttons: [&'static str; 3],
+    message_queue: MessageQueue,
+}
+
+impl AttributesScene {
+    pub fn new() -> Self {
+        AttributesScene {
+            selected_id: 0,
+            pending: Attributes::default(),
+            buttons: ["Confirm", "Reset", "Go back"],
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+
+    fn rows_count(&self) -> usize {
+        Attributes::NAMES.len() + self.buttons.len()
+    }
+
+    fn points_left(&self, data: &SharedData) -> u128 {
+        data.player_data.get_stat_points() - self.pending.total()
+    }
+}
+
+impl Scene for AttributesScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        let attributes_count = Attributes::NAMES.len();
+        match key.code {
+            KeyCode::Up if self.selected_id > 0 => self.selected_id -= 1,
+            KeyCode::Down if self.selected_id + 1 < self.rows_count() => self.selected_id += 1,
+            KeyCode::Right if self.selected_id < attributes_count && self.points_left(data) > 0 => {
+                *self.pending.get_mut(self.selected_id) += 1;
+            }
+            KeyCode::Left if self.selected_id < attributes_count => {
+                let points = self.pending.get_mut(self.selected_id);
+                *points = points.saturating_sub(1);
+            }
+            KeyCode::Enter if self.selected_id >= attributes_count => {
+                match self.buttons[self.selected_id - attributes_count] {
+                    "Confirm" => {
+                        if self.pending.total() == 0 {
+                            return;
+                        }
+                        data.player_data.allocate_attributes(&self.pending);
+                        self.pending = Attributes::default();
+                        self.message_queue
+                            .add_message("Stat points allocated!".into());
+                    }
+                    "Reset" => self.pending = Attributes::default(),
+                    "Go back" => data.current_scene = StatisticsScene::scene_id(),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
+        let highlighted = Style::default().bg(Color::Cyan);
+        let attributes = data.player_data.get_attributes();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw("Stat points left | "),
+                Span::styled(
+                    self.points_left(data).to_string(),
+                    Style::default().bold().fg(Color::Yellow),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        for (index, name) in Attributes::NAMES.iter().enumerate() {
+            let style = if self.selected_id == index {
+                highlighted
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![
+                Span::styled(format!("{:<9}", name), style),
+                Span::raw(" | "),
+                Span::styled(attributes.get(index).to_string(), Style::default().bold()),
+            ];
+            let pending = self.pending.get(index);
+            if pending > 0 {
+                spans.push(Span::styled(
+                    format!(" +{}", pending),
+                    Style::default().bold().light_green(),
+                ));
+            }
+            spans.push(Span::styled(
+                format!("  ({} per point)", Attributes::describe(index)),
+                Style::default().fg(Color::DarkGray),
+            ));
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+
+        let mut buttons_spans = vec![];
+        for (index, button) in self.buttons.iter().enumerate() {
+            if index > 0 {
+                buttons_spans.push(Span::raw("  "));
+            }
+            let style = if self.selected_id == Attributes::NAMES.len() + index {
+                highlighted
+            } else {
+                Style::default()
+            };
+            buttons_spans.push(Span::styled(*button, style));
+        }
+        lines.push(Line::from(buttons_spans));
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            "Left/Right to remove/add points".fg(Color::DarkGray),
+        ));
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
@@ -1,23 +1,35 @@
 use super::{
-    gains::{Gains, GainsScene},
+    gains::{finish_battle, sync_after_battle},
+    stats::StatisticsScene,
     Scene, SharedData,
 };
 use crate::game::{
     battle::{Battle, BattleWinner},
+    item::{ConsumableEffect, ItemType},
     message_queue::MessageQueue,
+    skill_tree::ActiveSkill,
 };
-use crossterm::event::{KeyEvent, KeyEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     prelude::Rect,
-    style::{Style, Stylize},
+    style::{Color, Style, Stylize},
     text::{Line, Span},
     widgets::Paragraph,
 };
 
 const SCENE_ID: i32 = 2;
 
+enum Stage {
+    Menu(usize),
+    ItemList(usize),
+    SkillList(usize),
+}
+
 pub struct BattleScene {
     battle: Battle,
+    stage: Stage,
+    actions: [&'static str; 3],
+    used_skills: Vec<ActiveSkill>,
     message_queue: MessageQueue,
 }
 impl BattleScene {
@@ -30,6 +42,9 @@ impl BattleScene {
         let bat: Battle = serde_json::from_str(str_data).unwrap();
         BattleScene {
             battle: bat,
+            stage: Stage::Menu(0),
+            actions: ["Attack", "Use item", "Skill"],
+            used_skills: vec![],
             message_queue: MessageQueue::default(),
         }
     }
@@ -37,6 +52,98 @@ impl BattleScene {
     pub fn scene_id() -> i32 {
         SCENE_ID
     }
+
+    fn finish(&mut self, winner: BattleWinner, data: &mut SharedData) {
+        let won = matches!(winner, BattleWinner::Player(_));
+        finish_battle(
+            data,
+            &self.battle.player,
+            &self.battle.enemy,
+            won,
+            self.battle.get_damage_dealt(),
+            won as u128,
+        );
+    }
+
+    fn escape(&mut self, data: &mut SharedData) {
+        sync_after_battle(data, &self.battle.player);
+        self.message_queue
+            .add_message(format!("You fled from {}", self.battle.enemy.get_name()));
+        data.current_scene = StatisticsScene::scene_id();
+    }
+
+    fn use_item(&mut self, index: usize, data: &mut SharedData) {
+        let Some(item) = data.player_data.get_mut_inventory().take(index) else {
+            return;
+        };
+        if let ItemType::Consumable(effect) = &item.item_type {
+            match effect {
+                ConsumableEffect::Escape => self.escape(data),
+                _ => self.battle.use_consumable(effect),
+            }
+        }
+    }
+
+    /// Unlocked active skills that weren't used during this battle yet.
+    fn available_skills(&self, data: &SharedData) -> Vec<ActiveSkill> {
+        data.player_data
+            .active_skills()
+            .into_iter()
+            .filter(|skill| !self.used_skills.contains(skill))
+            .collect()
+    }
+
+    fn render_actions(&self, lines: &mut Vec<Line<'_>>, data: &SharedData) {
+        if !self.battle.is_players_turn() {
+            lines.push(Line::from(
+                "Press any key to continue...".fg(Color::DarkGray),
+            ));
+            return;
+        }
+        let highlighted = Style::default().bg(Color::Cyan);
+        match self.stage {
+            Stage::Menu(selected) => {
+                let mut spans = vec![];
+                for (index, action) in self.actions.iter().enumerate() {
+                    if index > 0 {
+                        spans.push(Span::raw("  "));
+                    }
+                    let style = if index == selected {
+                        highlighted
+                    } else {
+                        Style::default()
+                    };
+                    spans.push(Span::styled(*action, style));
+                }
+                lines.push(Line::from(spans));
+            }
+            Stage::ItemList(selected) => {
+                lines.push(Line::from("Choose an item (Esc to go back)".bold()));
+                for (index, stack) in data.player_data.get_inventory().stacks().iter().enumerate() {
+                    let style = if index == selected {
+                        highlighted
+                    } else {
+                        Style::default()
+                    };
+                    lines.push(Line::from(Span::styled(
+                        format!("{}x {}", stack.amount, &stack.item.name),
+                        style,
+                    )));
+                }
+            }
+            Stage::SkillList(selected) => {
+                lines.push(Line::from("Choose a skill (Esc to go back)".bold()));
+                for (index, skill) in self.available_skills(data).iter().enumerate() {
+                    let style = if index == selected {
+                        highlighted
+                    } else {
+                        Style::default()
+                    };
+                    lines.push(Line::from(Span::styled(skill.name(), style)));
+                }
+            }
+        }
+    }
 }
 
 impl Scene for BattleScene {
@@ -52,43 +159,72 @@ impl Scene for BattleScene {
         if key.kind != KeyEventKind::Press {
             return;
         }
-        self.battle.tick();
-        if let Some(winner) = self.battle.get_winner() {
-            let xp_gain: u128;
-            let coins_gain: u128;
-            let won: bool;
-            let left_health: u128;
-            match winner {
-                BattleWinner::Player(_) => {
-                    xp_gain = 120;
-                    coins_gain = 15;
-                    won = true;
-                    left_health = self.battle.player.get_health();
-                }
-                BattleWinner::Enemy(_) => {
-                    xp_gain = 50;
-                    coins_gain = 0;
-                    won = false;
-                    left_health = self.battle.enemy.get_health();
-                }
+        if !self.battle.is_players_turn() {
+            self.battle.tick();
+        } else {
+            match self.stage {
+                Stage::Menu(selected) => match key.code {
+                    KeyCode::Left if selected > 0 => self.stage = Stage::Menu(selected - 1),
+                    KeyCode::Right if selected + 1 < self.actions.len() => {
+                        self.stage = Stage::Menu(selected + 1)
+                    }
+                    KeyCode::Enter => match self.actions[selected] {
+                        "Attack" => self.battle.tick(),
+                        "Use item" => {
+                            if data.player_data.get_inventory().is_empty() {
+                                self.message_queue
+                                    .add_message("You have no items to use".into());
+                            } else {
+                                self.stage = Stage::ItemList(0);
+                            }
+                        }
+                        "Skill" => {
+                            if self.available_skills(data).is_empty() {
+                                self.message_queue
+                                    .add_message("You have no skills to use".into());
+                            } else {
+                                self.stage = Stage::SkillList(0);
+                            }
+                        }
+                        _ => (),
+                    },
+                    _ => (),
+                },
+                Stage::SkillList(selected) => match key.code {
+                    KeyCode::Up if selected > 0 => self.stage = Stage::SkillList(selected - 1),
+                    KeyCode::Down if selected + 1 < self.available_skills(data).len() => {
+                        self.stage = Stage::SkillList(selected + 1)
+                    }
+                    KeyCode::Esc => self.stage = Stage::Menu(2),
+                    KeyCode::Enter => {
+                        self.stage = Stage::Menu(0);
+                        if let Some(skill) = self.available_skills(data).get(selected).copied() {
+                            self.used_skills.push(skill);
+                            self.battle.use_active_skill(skill);
+                        }
+                    }
+                    _ => (),
+                },
+                Stage::ItemList(selected) => match key.code {
+                    KeyCode::Up if selected > 0 => self.stage = Stage::ItemList(selected - 1),
+                    KeyCode::Down if selected + 1 < data.player_data.get_inventory().len() => {
+                        self.stage = Stage::ItemList(selected + 1)
+                    }
+                    KeyCode::Esc => self.stage = Stage::Menu(1),
+                    KeyCode::Enter => {
+                        self.stage = Stage::Menu(0);
+                        self.use_item(selected, data);
+                    }
+                    _ => (),
+                },
             }
-            data.player_data.add_xp(xp_gain);
-            data.player_data.add_coins(coins_gain);
-
-            let gains = Gains {
-                player_won: won,
-                enemy_name: self.battle.enemy.get_name().to_string(),
-                left_hp: left_health,
-
-                xp: xp_gain,
-                coins: coins_gain,
-            };
-            data.scene_data_transfer = Some(serde_json::to_string(&gains).unwrap());
-            data.current_scene = GainsScene::scene_id();
+        }
+        if let Some(winner) = self.battle.get_winner() {
+            self.finish(winner, data);
         }
     }
 
-    fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
         let mut lines: Vec<Line<'_>> = vec![];
         let player = &self.battle.player;
         let enemy = &self.battle.enemy;
@@ -119,6 +255,8 @@ impl Scene for BattleScene {
             Span::raw(enemy.get_health().to_string()),
             Span::styled(" HP", Style::default().light_red().bold()),
         ]));
+        lines.push(empty.clone());
+        self.render_actions(&mut lines, data);
 
         let p = Paragraph::new(lines);
         let area = Rect {
@@ -0,0 +1,234 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::{crafting::CraftingData, item::Item, message_queue::MessageQueue};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use rand::Rng;
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 5;
+
+#[derive(PartialEq)]
+enum Tab {
+    Craft,
+    Upgrade,
+}
+
+pub struct CraftingScene {
+    data: CraftingData,
+    tab: Tab,
+    selected_id: usize,
+    message_queue: MessageQueue,
+}
+
+impl CraftingScene {
+    pub fn new() -> Self {
+        CraftingScene {
+            data: CraftingData::load(),
+            tab: Tab::Craft,
+            selected_id: 0,
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+
+    fn entries_count(&self) -> usize {
+        match self.tab {
+            Tab::Craft => self.data.recipes.len(),
+            Tab::Upgrade => 2,
+        }
+    }
+
+    fn craft(&mut self, data: &mut SharedData) {
+        let recipe = &self.data.recipes[self.selected_id - 1];
+        if !data.player_data.remove_materials(&recipe.ingredients) {
+            let msg = format!("Not enough materials to craft {}", &recipe.result.name);
+            self.message_queue.add_message(msg);
+            return;
+        }
+        data.player_data.receive_item(recipe.result.clone());
+        let msg = format!("Succesfully crafted {}!", &recipe.result.name);
+        self.message_queue.add_message(msg);
+    }
+
+    fn upgrade(&mut self, data: &mut SharedData) {
+        let coins = data.player_data.get_coins();
+        let equipment = data.player_data.get_mut_equipment();
+        let slot = if self.selected_id == 1 {
+            &mut equipment.sword
+        } else {
+            &mut equipment.shield
+        };
+        let Some(item) = slot else {
+            self.message_queue
+                .add_message("There is nothing to upgrade".into());
+            return;
+        };
+        let cost = item.upgrade_cost();
+        if coins < cost {
+            let msg = format!("Not enough coins to upgrade {}", item.display_name());
+            self.message_queue.add_message(msg);
+            return;
+        }
+        let msg = if rand::thread_rng().gen_range(0..100) < item.upgrade_chance() {
+            item.upgrade();
+            format!("Upgrade succeeded! You now have {}", item.display_name())
+        } else {
+            format!("Upgrade of {} failed...", item.display_name())
+        };
+        data.player_data.spend_coins(cost);
+        self.message_queue.add_message(msg);
+    }
+
+    fn craft_lines(&self, data: &SharedData) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+        for (index, recipe) in self.data.recipes.iter().enumerate() {
+            let mut style = Style::default();
+            if !data.player_data.has_materials(&recipe.ingredients) {
+                style = style.fg(Color::DarkGray);
+            }
+            if self.selected_id == index + 1 {
+                style = style.bg(Color::Cyan);
+            }
+            let ingredients = recipe
+                .ingredients
+                .iter()
+                .map(|(name, amount)| format!("{}x {}", amount, name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(Line::from(Span::styled(
+                format!("[{}] {} - {}", index, &recipe.result.name, ingredients),
+                style,
+            )));
+        }
+        lines
+    }
+
+    fn upgrade_lines(&self, data: &SharedData) -> Vec<Line<'static>> {
+        let equipment = data.player_data.get_equipment();
+        let slots: [(&str, &Option<Item>); 2] =
+            [("Sword ", &equipment.sword), ("Shield", &equipment.shield)];
+        let mut lines = vec![];
+        for (index, (slot_name, item)) in slots.into_iter().enumerate() {
+            let mut style = Style::default();
+            if self.selected_id == index + 1 {
+                style = style.bg(Color::Cyan);
+            }
+            let text = match item {
+                Some(item) => format!(
+                    "{} | {} - {} c. ({}% chance)",
+                    slot_name,
+                    item.display_name(),
+                    item.upgrade_cost(),
+                    item.upgrade_chance()
+                ),
+                None => format!("{} | ", slot_name),
+            };
+            lines.push(Line::from(Span::styled(text, style)));
+        }
+        lines
+    }
+}
+
+impl Scene for CraftingScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        match key.code {
+            KeyCode::Left | KeyCode::Right => {
+                self.tab = if self.tab == Tab::Craft {
+                    Tab::Upgrade
+                } else {
+                    Tab::Craft
+                };
+                self.selected_id = 0;
+            }
+            KeyCode::Up if self.selected_id > 0 => self.selected_id -= 1,
+            KeyCode::Down => {
+                self.selected_id = std::cmp::min(self.entries_count(), self.selected_id + 1)
+            }
+            KeyCode::Enter => {
+                if self.selected_id == 0 {
+                    data.current_scene = StatisticsScene::scene_id();
+                    return;
+                }
+                match self.tab {
+                    Tab::Craft => self.craft(data),
+                    Tab::Upgrade => self.upgrade(data),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
+        let highlighted = Style::default().on_cyan();
+        let tab_style = |tab: Tab| {
+            if self.tab == tab {
+                Style::default().bold().underlined()
+            } else {
+                Style::default()
+            }
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Craft", tab_style(Tab::Craft)),
+                Span::raw("  "),
+                Span::styled("Upgrade", tab_style(Tab::Upgrade)),
+            ]),
+            Line::from(Span::styled(
+                "Go back",
+                if self.selected_id == 0 {
+                    highlighted
+                } else {
+                    Style::default()
+                },
+            )),
+            Line::from(""),
+        ];
+        match self.tab {
+            Tab::Craft => lines.extend(self.craft_lines(data)),
+            Tab::Upgrade => lines.extend(self.upgrade_lines(data)),
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Materials".bold()));
+        let materials = data.player_data.get_materials();
+        if materials.is_empty() {
+            lines.push(Line::from("You have no materials yet".dark_gray()));
+        }
+        for (name, amount) in materials {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}x ", amount), Style::default().bold()),
+                Span::raw(name.clone()),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Left/Right to switch tabs".fg(Color::DarkGray)));
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
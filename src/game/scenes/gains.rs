@@ -1,5 +1,15 @@
-use super::{stats::StatisticsScene, Scene, SharedData};
-use crate::game::message_queue::MessageQueue;
+use super::{new_game::NewGameScene, stats::StatisticsScene, Scene, SharedData};
+use crate::game::{
+    achievements::GameEvent,
+    balance::balance,
+    battle::Entity,
+    crafting::CraftingData,
+    hall_of_fame::{add_memorial, Memorial},
+    message_queue::MessageQueue,
+    numeric::{capped_add, capped_mul},
+    player::Player,
+    utils::delete_save,
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     prelude::Rect,
@@ -19,6 +29,117 @@ pub struct Gains {
 
     pub coins: u128,
     pub xp: u128,
+    #[serde(default)]
+    pub materials: Vec<(String, u128)>,
+    #[serde(default)]
+    pub penalty: DefeatOutcome,
+    #[serde(default)]
+    pub enemies_defeated: u128,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct DefeatOutcome {
+    pub coins_lost: u128,
+    pub xp_lost: u128,
+    /// Hardcore character died for good.
+    pub died: bool,
+}
+
+/// Carries the equipment wear and health left after a fight back to the
+/// player and drops any temporary buffs.
+pub fn sync_after_battle(data: &mut SharedData, player: &Entity) {
+    *data.player_data.get_mut_equipment() = player.get_equipment().clone();
+    data.player_data.clear_buffs();
+    data.player_data.set_current_health(player.get_health());
+}
+
+/// Hands out rewards or penalties for a finished fight and moves on to the
+/// gains screen. Shared by the turn based and the platformer battles.
+/// Every defeated enemy is worth a win, even if the fight was lost later.
+pub fn finish_battle(
+    data: &mut SharedData,
+    player: &Entity,
+    enemy: &Entity,
+    won: bool,
+    damage_dealt: u128,
+    enemies_defeated: u128,
+) {
+    let rewards = &balance().rewards;
+    let mut xp = capped_mul(rewards.win.xp, enemies_defeated);
+    let mut coins = capped_mul(rewards.win.coins, enemies_defeated);
+    if !won {
+        xp = capped_add(xp, rewards.loss.xp);
+        coins = capped_add(coins, rewards.loss.coins);
+    }
+    let mut materials: Vec<(String, u128)> = vec![];
+    let crafting = CraftingData::load();
+    for _ in 0..enemies_defeated {
+        for (material, amount) in crafting.roll_drops(&mut rand::thread_rng()) {
+            match materials.iter_mut().find(|(name, _)| *name == material) {
+                Some((_, total)) => *total = capped_add(*total, amount),
+                None => materials.push((material, amount)),
+            }
+        }
+    }
+    let mut penalty = DefeatOutcome::default();
+    if won {
+        data.player_data.record_event(GameEvent::BattleWon);
+    }
+    let difficulty = data.player_data.get_difficulty();
+    let xp_gain = difficulty.scale_reward(xp);
+    let coins_gain = difficulty.scale_reward(coins);
+    data.player_data
+        .get_mut_lifetime_stats()
+        .record_battle(won, damage_dealt);
+    data.player_data.add_xp(xp_gain);
+    data.player_data.add_coins(coins_gain);
+    for (material, amount) in &materials {
+        data.player_data.add_material(material, *amount);
+    }
+    sync_after_battle(data, player);
+    if !won {
+        penalty = apply_defeat(data, enemy.get_name());
+    }
+
+    let gains = Gains {
+        player_won: won,
+        enemy_name: enemy.get_name().to_string(),
+        left_hp: if won {
+            player.get_health()
+        } else {
+            enemy.get_health()
+        },
+
+        xp: xp_gain,
+        coins: coins_gain,
+        materials,
+        penalty,
+        enemies_defeated,
+    };
+    data.scene_data_transfer = Some(serde_json::to_string(&gains).unwrap());
+    data.current_scene = GainsScene::scene_id();
+}
+
+/// Punishes the player for a lost battle. Hardcore characters die, their
+/// save is deleted and they get a place in the hall of fame.
+pub fn apply_defeat(data: &mut SharedData, enemy_name: &str) -> DefeatOutcome {
+    let player = &mut data.player_data;
+    let (coins_lost, xp_lost) = player.apply_defeat_penalty();
+    let died = player.is_hardcore();
+    if died {
+        add_memorial(Memorial {
+            name: player.get_name().into(),
+            class: player.get_class(),
+            level: player.get_level(),
+            killed_by: enemy_name.into(),
+        });
+        delete_save(player.get_save_slot());
+    }
+    DefeatOutcome {
+        coins_lost,
+        xp_lost,
+        died,
+    }
 }
 
 pub struct GainsScene {
@@ -58,7 +179,14 @@ impl Scene for GainsScene {
             return;
         }
         if let KeyCode::Enter = key.code {
-            data.current_scene = StatisticsScene::scene_id()
+            if self.gains.penalty.died {
+                let msg_queue = data.player_data.get_message_queue();
+                data.player_data = Player::default();
+                data.player_data.set_message_queue(msg_queue);
+                data.current_scene = NewGameScene::scene_id();
+            } else {
+                data.current_scene = StatisticsScene::scene_id()
+            }
         }
     }
 
@@ -94,6 +222,15 @@ impl Scene for GainsScene {
                 Span::raw(" hp left."),
             ]));
         }
+        if self.gains.enemies_defeated > 1 {
+            lines.push(Line::from(vec![
+                Span::raw("Enemies defeated: "),
+                Span::styled(
+                    self.gains.enemies_defeated.to_string(),
+                    Style::default().bold(),
+                ),
+            ]));
+        }
         lines.push(empty.clone());
         if self.gains.coins > 0 {
             lines.push(Line::from(vec![
@@ -113,6 +250,40 @@ impl Scene for GainsScene {
                 Span::styled(" xp", Style::default().bold().light_blue()),
             ]));
         }
+        for (material, amount) in &self.gains.materials {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("+{}", amount),
+                    Style::default().bold().light_green(),
+                ),
+                Span::styled(format!(" {}", material), Style::default().bold().gray()),
+            ]));
+        }
+        if self.gains.penalty.coins_lost > 0 {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("-{}", self.gains.penalty.coins_lost),
+                    Style::default().bold().light_red(),
+                ),
+                Span::styled(" coins", Style::default().bold().light_yellow()),
+            ]));
+        }
+        if self.gains.penalty.xp_lost > 0 {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("-{}", self.gains.penalty.xp_lost),
+                    Style::default().bold().light_red(),
+                ),
+                Span::styled(" xp", Style::default().bold().light_blue()),
+            ]));
+        }
+        if self.gains.penalty.died {
+            lines.push(empty.clone());
+            lines.push(Line::from(
+                "You died. Your hardcore journey is over.".bold().red(),
+            ));
+            lines.push(Line::from("Your name was carved into the hall of fame."));
+        }
         lines.push(empty.clone());
         lines.push(Line::from("Press Enter to continue..."));
 
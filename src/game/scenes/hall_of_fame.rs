@@ -0,0 +1,88 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::{
+    hall_of_fame::{load_hall_of_fame, Memorial},
+    message_queue::MessageQueue,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 10;
+
+pub struct HallOfFameScene {
+    memorials: Vec<Memorial>,
+    message_queue: MessageQueue,
+}
+
+impl HallOfFameScene {
+    pub fn new() -> Self {
+        let mut memorials = load_hall_of_fame();
+        memorials.sort_by_key(|memorial| std::cmp::Reverse(memorial.level));
+        HallOfFameScene {
+            memorials,
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+}
+
+impl Scene for HallOfFameScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if let KeyCode::Enter | KeyCode::Esc = key.code {
+            data.current_scene = StatisticsScene::scene_id()
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
+        let mut lines = vec![Line::from("Hall of fame".bold()), Line::from("")];
+        if self.memorials.is_empty() {
+            lines.push(Line::from(
+                "No hardcore hero has fallen yet".fg(Color::DarkGray),
+            ));
+        }
+        for memorial in &self.memorials {
+            lines.push(Line::from(vec![
+                Span::styled(memorial.name.clone(), Style::default().bold()),
+                Span::raw(format!(
+                    ", level {} {}",
+                    memorial.level,
+                    memorial.class.name()
+                )),
+                Span::styled(
+                    format!(" - slain by {}", memorial.killed_by),
+                    Style::default().fg(Color::LightRed),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Press Enter to go back..."));
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
@@ -0,0 +1,100 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::message_queue::MessageQueue;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 6;
+
+pub struct InventoryScene {
+    selected_id: usize,
+    message_queue: MessageQueue,
+}
+
+impl InventoryScene {
+    pub fn new() -> Self {
+        InventoryScene {
+            selected_id: 0,
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+}
+
+impl Scene for InventoryScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        let items_count = data.player_data.get_inventory().len();
+        match key.code {
+            KeyCode::Up if self.selected_id > 0 => self.selected_id -= 1,
+            KeyCode::Down => self.selected_id = std::cmp::min(items_count, self.selected_id + 1),
+            KeyCode::Enter => {
+                if self.selected_id == 0 {
+                    data.current_scene = StatisticsScene::scene_id();
+                    return;
+                }
+                data.player_data.use_consumable(self.selected_id - 1);
+                let items_count = data.player_data.get_inventory().len();
+                self.selected_id = std::cmp::min(items_count, self.selected_id);
+            }
+            _ => (),
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
+        let highlighted = Style::default().bg(Color::Cyan);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Go back",
+                if self.selected_id == 0 {
+                    highlighted
+                } else {
+                    Style::default()
+                },
+            )),
+            Line::from(""),
+        ];
+        let inventory = data.player_data.get_inventory();
+        if inventory.is_empty() {
+            lines.push(Line::from("Your inventory is empty".dark_gray()));
+        }
+        for (index, stack) in inventory.stacks().iter().enumerate() {
+            let style = if self.selected_id == index + 1 {
+                highlighted
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}x ", stack.amount), Style::default().bold()),
+                Span::styled(stack.item.name.clone(), style),
+            ]));
+        }
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
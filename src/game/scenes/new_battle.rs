@@ -1,10 +1,31 @@
 #![allow(unused)]
 use std::ops::Index;
 
-use super::{stats::StatisticsScene, Scene, SharedData};
-use crate::game::{battle::Entity, message_queue::MessageQueue, utils::get_full_size_rect};
+use super::{
+    arena_select::BattleSetup,
+    gains::{finish_battle, sync_after_battle},
+    stats::StatisticsScene,
+    Scene, SharedData, TICK,
+};
+use crate::game::{
+    arena::{load_arenas, Tile},
+    arena_generator::generate,
+    battle::{Battle, Entity},
+    collision::{covered_cells, move_and_collide, touches, Aabb},
+    difficulty::Difficulty,
+    enemy::{AttackKind, Behavior, ENEMY_TEMPLATES},
+    item::{ConsumableEffect, ItemType},
+    message_queue::MessageQueue,
+    numeric::{capped_add, capped_mul},
+    physics::{
+        jump_reach, AIR_DRAG, GRAVITY, GROUND_FRICTION, GROUND_GRIP, JUMP_HOLD_ACCELERATION,
+        JUMP_SPEED, MAX_FALL_SPEED, MAX_SPEED, WALK_ACCELERATION,
+    },
+    skill_tree::ActiveSkill,
+    utils::{calculate_bar, get_full_size_rect},
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use rand::{rngs::ThreadRng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use ratatui::{
     prelude::*,
     text::Line,
@@ -13,27 +34,104 @@ use ratatui::{
 
 const SCENE_ID: i32 = 69;
 
+/// Seconds simulated by one tick. Like the movement constants in `physics`,
+/// every constant below is in cells and seconds.
+const DT: f64 = TICK.as_secs_f64();
+
+const KNOCKBACK_SPEED: f64 = 48.0;
+const KNOCKBACK_LIFT: f64 = 18.0;
+/// Size of a projectile, in cells.
+const PROJECTILE_SIZE: f64 = 0.2;
+
+/// Cooldowns between attacks, in seconds.
+const MELEE_COOLDOWN: f64 = 0.33;
+const RANGED_COOLDOWN: f64 = 0.75;
+const ENEMY_MELEE_COOLDOWN: f64 = 0.67;
+const ENEMY_RANGED_COOLDOWN: f64 = 1.5;
+/// How many cells in front of the attacker a melee swing reaches.
+const MELEE_REACH: f64 = 2.5;
+const PROJECTILE_SPEED: f64 = 72.0;
+/// Seconds a melee swing stays visible.
+const SWING_TIME: f64 = 0.1;
+const MAX_WAVE_SIZE: usize = 3;
+const CLIMB_SPEED: f64 = 12.0;
+const BOUNCE_SPEED: f64 = 42.0;
+/// Spikes take this share of maximum health, in percent.
+const SPIKE_DAMAGE: u128 = 10;
+/// Seconds spikes leave someone alone after hurting them.
+const SPIKE_COOLDOWN: f64 = 1.0;
+/// Seconds a notice stays in place of the controls hint.
+const NOTICE_TIME: f64 = 2.0;
+/// Keeps an entity standing exactly on a platform from falling through it.
+const FOOTING: f64 = 1e-6;
+
+#[derive(Clone, Copy, PartialEq)]
 enum MoveDirection {
     Left,
     Right,
     Up,
 }
 
+impl MoveDirection {
+    fn opposite(&self) -> MoveDirection {
+        match self {
+            MoveDirection::Left => MoveDirection::Right,
+            MoveDirection::Right => MoveDirection::Left,
+            MoveDirection::Up => MoveDirection::Up,
+        }
+    }
+}
+
+/// What an entity wants to do this tick, either from the keyboard or the AI.
+#[derive(Clone, Copy)]
+struct Controls {
+    left: bool,
+    right: bool,
+    jump: bool,
+    /// Climb ladders, `down` also drops through platforms.
+    up: bool,
+    down: bool,
+    speed: f64,
+}
+
+impl Controls {
+    fn new(speed: f64) -> Controls {
+        Controls {
+            left: false,
+            right: false,
+            jump: false,
+            up: false,
+            down: false,
+            speed,
+        }
+    }
+
+    fn walk(&mut self, direction: MoveDirection) {
+        match direction {
+            MoveDirection::Left => self.left = true,
+            MoveDirection::Right => self.right = true,
+            MoveDirection::Up => self.jump = true,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 struct EntityPos {
     x: f64,
     y: f64,
     xa: f64,
     ya: f64,
+    climbing: bool,
 }
 
-impl From<EntityPos> for Rect {
-    fn from(val: EntityPos) -> Self {
-        Rect {
-            x: val.x as u16,
-            y: val.y as u16,
-            width: 1,
-            height: 1,
+impl EntityPos {
+    /// Every entity takes up exactly one cell.
+    fn aabb(&self) -> Aabb {
+        Aabb {
+            x: self.x,
+            y: self.y,
+            width: 1.0,
+            height: 1.0,
         }
     }
 }
@@ -41,12 +139,137 @@ impl From<EntityPos> for Rect {
 struct EntityState {
     pos: EntityPos,
     entity: Entity,
+    facing: MoveDirection,
+    /// Seconds until the next attack is allowed.
+    cooldown: f64,
+    /// Seconds the current melee swing is still shown for.
+    swing: f64,
+    /// Seconds until spikes can hurt again.
+    spike_cooldown: f64,
+}
+
+impl EntityState {
+    fn new(x: f64, y: f64, entity: Entity, facing: MoveDirection) -> EntityState {
+        EntityState {
+            pos: EntityPos {
+                x,
+                y,
+                xa: 0.0,
+                ya: 0.0,
+                climbing: false,
+            },
+            entity,
+            facing,
+            cooldown: 0.0,
+            swing: 0.0,
+            spike_cooldown: 0.0,
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        self.entity.get_health() > 0
+    }
+
+    /// Whether a melee swing of this entity reaches the given position.
+    fn reaches(&self, target: &EntityPos) -> bool {
+        let dx = match self.facing {
+            MoveDirection::Left => self.pos.x - target.x,
+            _ => target.x - self.pos.x,
+        };
+        (0.0..=MELEE_REACH).contains(&dx) && (target.y - self.pos.y).abs() < 1.0
+    }
+
+    fn hurt_by_spikes(&mut self) {
+        let damage = (self.entity.get_max_health() * SPIKE_DAMAGE / 100).max(1);
+        self.entity.take_damage(damage);
+        self.pos.climbing = false;
+        self.pos.ya = -KNOCKBACK_LIFT;
+        self.spike_cooldown = SPIKE_COOLDOWN;
+    }
 }
 
-#[derive(Clone)]
-enum Tile {
-    Empty,
-    Full,
+struct Enemy {
+    state: EntityState,
+    symbol: char,
+    behavior: Behavior,
+    attack: AttackKind,
+    speed: f64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Shooter {
+    Player,
+    /// Index into the enemies of the current wave.
+    Enemy(usize),
+}
+
+struct Projectile {
+    x: f64,
+    y: f64,
+    xa: f64,
+    shooter: Shooter,
+}
+
+impl Projectile {
+    fn aabb(&self) -> Aabb {
+        Aabb {
+            x: self.x,
+            y: self.y,
+            width: PROJECTILE_SIZE,
+            height: PROJECTILE_SIZE,
+        }
+    }
+}
+
+/// The part of the arena that is on screen. Follows the player on arenas
+/// larger than the terminal and centers smaller ones.
+struct Camera {
+    /// Arena cell shown in the top left corner.
+    left: i64,
+    top: i64,
+    /// Where on screen the arena is drawn.
+    area: Rect,
+}
+
+impl Camera {
+    /// Leaves a row above and below the arena for the status lines.
+    fn new(screen: Rect, grid_size: &GridSize, target: &EntityPos) -> Camera {
+        let view = Rect {
+            y: screen.y + 1,
+            height: screen.height.saturating_sub(2),
+            ..screen
+        };
+        let (left, x, width) = Camera::axis(view.width, grid_size.width, target.x);
+        let (top, y, height) = Camera::axis(view.height, grid_size.height, target.y);
+        Camera {
+            left,
+            top,
+            area: Rect {
+                x: view.x + x,
+                y: view.y + y,
+                width,
+                height,
+            },
+        }
+    }
+
+    /// First visible cell, screen offset and visible length along one axis.
+    fn axis(view: u16, arena: usize, target: f64) -> (i64, u16, u16) {
+        if arena <= view as usize {
+            return (0, (view - arena as u16) / 2, arena as u16);
+        }
+        let first = (target + 0.5 - view as f64 / 2.0).round() as i64;
+        (first.clamp(0, (arena - view as usize) as i64), 0, view)
+    }
+
+    /// Screen position of an arena cell, `None` if it's out of view.
+    fn to_screen(&self, x: f64, y: f64) -> Option<(u16, u16)> {
+        let column = x.floor() as i64 - self.left;
+        let row = y.floor() as i64 - self.top;
+        let visible = (0..self.area.width as i64).contains(&column)
+            && (0..self.area.height as i64).contains(&row);
+        visible.then(|| (self.area.x + column as u16, self.area.y + row as u16))
+    }
 }
 
 struct GridSize {
@@ -59,265 +282,874 @@ pub struct NBattleScene {
     grid: Vec<Vec<Tile>>,
 
     player_state: EntityState,
-    enemy_state: EntityState,
+    /// Enemies of the current wave, defeated ones stay until it's cleared.
+    enemies: Vec<Enemy>,
+    enemy_spawns: Vec<(usize, usize)>,
+    difficulty: Difficulty,
+    /// Current wave, starting at 1.
+    wave: u32,
+    waves: u32,
+    /// Enemies defeated in the waves before the current one.
+    defeated_before: u128,
+    projectiles: Vec<Projectile>,
+    damage_dealt: u128,
 
     pressed_keys: Vec<KeyCode>,
-    rng_thread: ThreadRng,
+    /// Short feedback shown below the arena. A message would block input
+    /// while the fight goes on.
+    notice: String,
+    /// Seconds the notice is still shown for.
+    notice_time: f64,
+    /// Inventory stack used by the item key.
+    selected_item: usize,
+    /// Index into `available_skills`.
+    selected_skill: usize,
+    used_skills: Vec<ActiveSkill>,
+    /// Shown during the fight so it can be replayed.
+    seed: u64,
+    rng: StdRng,
+    /// Problems with the setup, shown once the message queue is set.
+    errors: Vec<String>,
     message_queue: MessageQueue,
 }
 
 impl NBattleScene {
     pub fn new(data: &SharedData) -> Self {
+        let mut errors = vec![];
+        let random_setup = || BattleSetup {
+            seed: rand::thread_rng().gen(),
+            arena: None,
+            waves: 1,
+        };
+        let setup = match &data.scene_data_transfer {
+            Some(json) => serde_json::from_str(json).unwrap_or_else(|err| {
+                errors.push(format!(
+                    "Couldn't read the battle setup, fighting in a random arena. {}",
+                    err
+                ));
+                random_setup()
+            }),
+            None => random_setup(),
+        };
+        let (arenas, _) = load_arenas();
+        let difficulty = data.player_data.get_difficulty();
+        let arena = setup
+            .arena
+            .and_then(|name| {
+                let arena = arenas.into_iter().find(|arena| arena.name == name);
+                if arena.is_none() {
+                    errors.push(format!(
+                        "Arena {} couldn't be loaded, fighting in a random one",
+                        name
+                    ));
+                }
+                arena
+            })
+            .unwrap_or_else(|| generate(setup.seed, difficulty, jump_reach()));
+        let (player_x, player_y) = arena.player_spawn;
         let mut scene = NBattleScene {
             grid_size: GridSize {
-                width: 120,
-                height: 33,
-            },
-            grid: vec![vec![Tile::Empty; 120]; 33],
-
-            player_state: EntityState {
-                pos: EntityPos {
-                    x: 3.0,
-                    y: 20.0,
-                    xa: 0.0,
-                    ya: 0.0,
-                },
-                entity: data.player_data.to_entity(),
-            },
-            enemy_state: EntityState {
-                pos: EntityPos {
-                    x: 100.0,
-                    y: 20.0,
-                    xa: 0.0,
-                    ya: 0.0,
-                },
-                entity: Entity::default(),
+                width: arena.width,
+                height: arena.height,
             },
+            grid: arena.tiles,
+
+            player_state: EntityState::new(
+                player_x as f64,
+                player_y as f64,
+                data.player_data.to_entity(),
+                MoveDirection::Right,
+            ),
+            enemies: vec![],
+            enemy_spawns: arena.enemy_spawns,
+            difficulty,
+            wave: 0,
+            waves: setup.waves.max(1),
+            defeated_before: 0,
+            projectiles: vec![],
+            damage_dealt: 0,
 
             pressed_keys: vec![],
-            rng_thread: rand::thread_rng(),
+            notice: String::new(),
+            notice_time: 0.0,
+            selected_item: 0,
+            selected_skill: 0,
+            used_skills: vec![],
+            seed: setup.seed,
+            rng: StdRng::seed_from_u64(setup.seed),
+            errors,
             message_queue: MessageQueue::default(),
         };
-        scene.generate_grid();
+        scene.next_wave();
+        if scene.enemies[0].state.pos.x < player_x as f64 {
+            scene.player_state.facing = MoveDirection::Left;
+        }
         scene
     }
 
-    fn set_cell(&mut self, x: usize, y: usize, tile: Tile) {
-        if x >= self.grid_size.width || y >= self.grid_size.height {
+    /// Wave `n` brings `n` enemies, up to `MAX_WAVE_SIZE` and never more
+    /// than the arena has enemy spawns, so no two share a cell.
+    fn next_wave(&mut self) {
+        self.defeated_before = capped_add(self.defeated_before, self.enemies.len() as u128);
+        self.wave += 1;
+        // Shots of the last wave would point at the enemies of the new one.
+        self.projectiles
+            .retain(|projectile| projectile.shooter == Shooter::Player);
+        let first_spawn = self.rng.gen_range(0..self.enemy_spawns.len());
+        let size = (self.wave as usize)
+            .min(MAX_WAVE_SIZE)
+            .min(self.enemy_spawns.len());
+        self.enemies = (0..size)
+            .map(|i| {
+                let (x, y) = self.enemy_spawns[(first_spawn + i) % self.enemy_spawns.len()];
+                let template = &ENEMY_TEMPLATES[self.rng.gen_range(0..ENEMY_TEMPLATES.len())];
+                let facing = if (x as f64) < self.player_state.pos.x {
+                    MoveDirection::Right
+                } else {
+                    MoveDirection::Left
+                };
+                Enemy {
+                    state: EntityState::new(
+                        x as f64,
+                        y as f64,
+                        template.to_entity(self.difficulty),
+                        facing,
+                    ),
+                    symbol: template.symbol,
+                    behavior: template.behavior,
+                    attack: template.attack,
+                    speed: template.speed,
+                }
+            })
+            .collect();
+    }
+
+    fn enemies_defeated(&self) -> u128 {
+        let defeated = self.enemies.iter().filter(|enemy| !enemy.state.is_alive());
+        capped_add(self.defeated_before, defeated.count() as u128)
+    }
+
+    fn render_grid(&self, frame: &mut crate::Frame, camera: &Camera) {
+        let area = camera.area;
+        let columns = camera.left as usize..camera.left as usize + area.width as usize;
+        let rows: Vec<Line> = self.grid[camera.top as usize..]
+            .iter()
+            .take(area.height as usize)
+            .map(|row| {
+                let spans: Vec<Span> = row[columns.clone()]
+                    .iter()
+                    .map(|tile| {
+                        let (glyph, color) = match tile {
+                            Tile::Empty => (" ", Color::Reset),
+                            Tile::Full => ("▀", Color::Reset),
+                            Tile::Platform => ("▔", Color::Gray),
+                            Tile::Ladder => ("H", Color::Yellow),
+                            Tile::Spikes => ("▲", Color::LightRed),
+                            Tile::Breakable => ("▒", Color::LightYellow),
+                            Tile::Bounce => ("▀", Color::LightMagenta),
+                        };
+                        Span::styled(glyph, Style::default().fg(color))
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(rows), area)
+    }
+
+    fn on_ground(&self, pos: &EntityPos) -> bool {
+        let feet = Some(pos.y + 1.0);
+        pos.ya >= 0.0 && touches(|x, y| self.blocks(x, y, feet), pos.aabb(), 0.0, 1.0)
+    }
+
+    /// Draws a single cell of the arena, given in grid coordinates.
+    fn render_cell(
+        &self,
+        frame: &mut crate::Frame,
+        camera: &Camera,
+        (x, y): (f64, f64),
+        glyph: &str,
+        color: Color,
+    ) {
+        let Some((x, y)) = camera.to_screen(x, y) else {
+            return;
+        };
+        let block = Block::default().title(glyph.to_string()).fg(color).bold();
+        let area = Rect {
+            x,
+            y,
+            width: 1,
+            height: 1,
+        };
+        frame.render_widget(block, area);
+    }
+
+    fn render_swing(
+        &self,
+        frame: &mut crate::Frame,
+        camera: &Camera,
+        state: &EntityState,
+        color: Color,
+    ) {
+        if state.swing <= 0.0 {
             return;
         }
-        self.grid[y][x] = tile;
+        let (x, glyph) = match state.facing {
+            MoveDirection::Left => (state.pos.x - 1.0, "\\"),
+            _ => (state.pos.x + 1.0, "/"),
+        };
+        self.render_cell(frame, camera, (x, state.pos.y), glyph, color);
     }
 
-    fn generate_grid(&mut self) {
-        let row = 30;
-        for column in 0..self.grid_size.width {
-            self.grid[row][column] = Tile::Full;
+    fn render_entities(&self, frame: &mut crate::Frame, camera: &Camera) {
+        for projectile in &self.projectiles {
+            let color = match projectile.shooter {
+                Shooter::Player => Color::LightGreen,
+                Shooter::Enemy(_) => Color::LightRed,
+            };
+            let position = (projectile.x, projectile.y);
+            self.render_cell(frame, camera, position, "•", color);
         }
+        for enemy in self.alive_enemies() {
+            self.render_swing(frame, camera, &enemy.state, Color::LightRed);
+        }
+        self.render_swing(frame, camera, &self.player_state, Color::LightGreen);
 
-        for x in 0..self.grid_size.width {
-            for y in 0..16 {
-                if self.rng_thread.gen_range(0..100) == 0 {
-                    for offset in -3..4 {
-                        let offset: i16 = offset;
-                        if offset.is_negative() && offset.unsigned_abs() as usize > x {
-                            continue;
-                        }
-                        let x = if offset.is_negative() {
-                            x - offset.unsigned_abs() as usize
-                        } else {
-                            x + offset as usize
-                        };
-                        self.set_cell(x, y * 2, Tile::Full)
-                    }
-                }
-            }
+        for enemy in self.alive_enemies() {
+            let symbol = enemy.symbol.to_string();
+            let position = (enemy.state.pos.x, enemy.state.pos.y);
+            self.render_cell(frame, camera, position, &symbol, Color::LightRed);
         }
+        let player_pos = self.player_state.pos;
+        let position = (player_pos.x, player_pos.y);
+        self.render_cell(frame, camera, position, "O", Color::LightGreen);
     }
 
-    fn render_grid(&self, frame: &mut crate::Frame) {
-        let mut rows: Vec<Line> = vec![];
-        for row in &self.grid {
-            let mut rend = String::from("");
-            for cell in row {
-                let ch = match cell {
-                    Tile::Empty => ' ',
-                    Tile::Full => '▀',
-                };
-                rend.push(ch);
-            }
-            rows.push(Line::from(rend))
+    fn health_spans(entity: &Entity, color: Color) -> Vec<Span<'static>> {
+        let (filled, missing) = calculate_bar(entity.get_health(), entity.get_max_health(), 10);
+        vec![
+            Span::raw(format!("{} ", entity.get_name())),
+            Span::styled(filled, Style::default().bold().fg(color)),
+            Span::styled(missing, Style::default().bold().fg(Color::DarkGray)),
+            Span::raw(format!(
+                " {}/{}",
+                entity.get_health(),
+                entity.get_max_health()
+            )),
+        ]
+    }
+
+    /// Health of everyone, the wave and the selected item and skill above
+    /// the arena, the controls below it.
+    fn render_status(&self, frame: &mut crate::Frame, camera: &Camera, data: &SharedData) {
+        let mut spans = NBattleScene::health_spans(&self.player_state.entity, Color::LightGreen);
+        if let Some(stack) = data.player_data.get_inventory().get(self.selected_item) {
+            spans.push(Span::styled(
+                format!("    {} x{}", stack.item.display_name(), stack.amount),
+                Style::default().fg(Color::LightCyan),
+            ));
         }
-        let p = Paragraph::new(rows);
+        if let Some(skill) = self.available_skills(data).get(self.selected_skill) {
+            spans.push(Span::styled(
+                format!("    {} (skill)", skill.name()),
+                Style::default().fg(Color::LightMagenta),
+            ));
+        }
+        if self.waves > 1 {
+            spans.push(Span::styled(
+                format!("    Wave {}/{}", self.wave, self.waves),
+                Style::default().bold().fg(Color::Yellow),
+            ));
+        }
+        for enemy in self.alive_enemies() {
+            spans.push(Span::raw("    "));
+            spans.extend(NBattleScene::health_spans(
+                &enemy.state.entity,
+                Color::LightRed,
+            ));
+        }
+        let screen = frame.size();
+        let area = Rect {
+            y: camera.area.y - 1,
+            height: 1,
+            ..screen
+        };
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+
+        let hint = if self.notice_time > 0.0 {
+            Span::styled(self.notice.clone(), Style::default().fg(Color::LightYellow))
+        } else {
+            Span::styled(
+                format!(
+                    "A/D move, W/S climb, Space jump, J melee, K shoot, Q/E item, R/F skill, Esc flee    Seed {}",
+                    self.seed
+                ),
+                Style::default().fg(Color::DarkGray),
+            )
+        };
         let area = Rect {
-            x: (frame.size().width - self.grid_size.width as u16) / 2,
-            y: (frame.size().height - self.grid_size.height as u16) / 2,
-            width: self.grid_size.width as u16,
-            height: self.grid_size.height as u16,
+            y: camera.area.bottom(),
+            height: 1,
+            ..screen
         };
-        frame.render_widget(p, area)
+        frame.render_widget(Paragraph::new(Line::from(hint)), area);
     }
 
-    fn on_ground(&self, entity: &EntityState) -> bool {
-        let pos = &entity.pos;
-        if pos.ya >= 0.0 {
-            if pos.y as usize == self.grid_size.height - 1 {
-                return true;
-            }
-            if let Tile::Full = self.grid[(pos.y + 1.0) as usize][pos.x as usize] {
-                return true;
-            }
+    /// Whether there is ground to stand on one cell ahead.
+    fn ground_ahead(&self, pos: &EntityPos, direction: MoveDirection) -> bool {
+        let x = match direction {
+            MoveDirection::Left if pos.x >= 1.0 => pos.x - 1.0,
+            MoveDirection::Right if pos.x + 1.0 < self.grid_size.width as f64 => pos.x + 1.0,
+            _ => return false,
+        };
+        self.on_ground(&EntityPos { x, ..*pos })
+    }
+
+    fn jump(&self, pos: &mut EntityPos) {
+        if pos.climbing {
+            pos.climbing = false;
+            pos.ya = -JUMP_SPEED;
+        } else if self.on_ground(pos) {
+            pos.ya -= JUMP_SPEED;
+        } else {
+            pos.ya -= JUMP_HOLD_ACCELERATION * DT;
         }
-        false
     }
 
-    fn render_entities(&self, frame: &mut crate::Frame) {
-        let block = Block::default().title("x").fg(Color::LightRed).bold();
-        let mut area: Rect = self.enemy_state.pos.into();
-        area.x += (frame.size().width - self.grid_size.width as u16) / 2;
-        area.y += (frame.size().height - self.grid_size.height as u16) / 2;
-        frame.render_widget(block, area);
+    /// `None` outside of the arena.
+    fn tile(&self, x: i64, y: i64) -> Option<Tile> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.grid
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+    }
 
-        let block = Block::default().title("O").fg(Color::LightGreen).bold();
-        let mut area: Rect = self.player_state.pos.into();
-        area.x += (frame.size().width - self.grid_size.width as u16) / 2;
-        area.y += (frame.size().height - self.grid_size.height as u16) / 2;
-        frame.render_widget(block, area);
+    /// Tiles outside of the arena count as solid walls.
+    fn is_solid(&self, x: i64, y: i64) -> bool {
+        self.tile(x, y).is_none_or(|tile| tile.is_solid())
+    }
+
+    /// Like `is_solid`, but platforms and the tops of ladders also hold up
+    /// whoever has their `feet` above them.
+    fn blocks(&self, x: i64, y: i64, feet: Option<f64>) -> bool {
+        let standable = match self.tile(x, y) {
+            Some(Tile::Platform) => true,
+            Some(Tile::Ladder) => self.tile(x, y - 1) != Some(Tile::Ladder),
+            _ => false,
+        };
+        self.is_solid(x, y) || (standable && feet.is_some_and(|feet| y as f64 >= feet - FOOTING))
+    }
+
+    fn touches_tile(&self, pos: &EntityPos, tile: Tile) -> bool {
+        covered_cells(pos.aabb()).any(|(x, y)| self.tile(x, y) == Some(tile))
+    }
+
+    /// Whether the entity stands right on top of the given tile.
+    fn stands_on(&self, pos: &EntityPos, tile: Tile) -> bool {
+        let below = EntityPos {
+            y: pos.y + 1.0,
+            ..*pos
+        };
+        self.touches_tile(&below, tile)
+    }
+
+    fn update_climbing(&self, pos: &mut EntityPos, controls: &Controls) {
+        let on_ladder = self.touches_tile(pos, Tile::Ladder);
+        if (controls.up && on_ladder)
+            || (controls.down && (on_ladder || self.stands_on(pos, Tile::Ladder)))
+        {
+            pos.climbing = true;
+        }
+        if pos.climbing {
+            pos.ya = match (controls.up, controls.down) {
+                (true, false) => -CLIMB_SPEED,
+                (false, true) => CLIMB_SPEED,
+                _ => 0.0,
+            };
+        }
+    }
+
+    fn can_move(&self, direction: &MoveDirection, pos: &EntityPos) -> bool {
+        let (dx, dy) = match direction {
+            MoveDirection::Left => (-1.0, 0.0),
+            MoveDirection::Right => (1.0, 0.0),
+            MoveDirection::Up => (0.0, -1.0),
+        };
+        !touches(|x, y| self.is_solid(x, y), pos.aabb(), dx, dy)
+    }
+
+    fn step(&self, pos: &mut EntityPos, direction: MoveDirection, speed: f64) {
+        if !self.can_move(&direction, pos) {
+            return;
+        }
+        let mut xaa: f64 = match direction {
+            MoveDirection::Left => -WALK_ACCELERATION * DT,
+            MoveDirection::Right => WALK_ACCELERATION * DT,
+            _ => panic!("Wrong move direction you idiot"),
+        };
+        xaa *= speed;
+        if pos.climbing || self.on_ground(pos) {
+            xaa *= GROUND_GRIP;
+        }
+        pos.xa += xaa;
     }
 
-    fn jump(&mut self) {
-        if self.on_ground(&self.player_state) {
-            self.player_state.pos.ya -= 0.2;
+    /// Applies controls, gravity and collisions, returning the new position.
+    fn move_entity(&self, mut pos: EntityPos, controls: Controls) -> EntityPos {
+        self.update_climbing(&mut pos, &controls);
+        // Dropping through a platform starts with a small push down.
+        if controls.down && !pos.climbing && self.on_ground(&pos) {
+            pos.ya = GRAVITY * DT;
+        }
+        if controls.right {
+            self.step(&mut pos, MoveDirection::Right, controls.speed)
+        }
+        if controls.left {
+            self.step(&mut pos, MoveDirection::Left, controls.speed)
+        }
+        if controls.jump && pos.ya <= 0.0 {
+            self.jump(&mut pos)
+        }
+
+        pos.xa = pos.xa.clamp(-MAX_SPEED, MAX_SPEED);
+        pos.ya = pos.ya.clamp(-MAX_FALL_SPEED, MAX_FALL_SPEED);
+
+        let feet = (pos.ya >= 0.0 && !controls.down).then_some(pos.y + 1.0);
+        let (aabb, collisions) = move_and_collide(
+            |x, y| self.blocks(x, y, feet),
+            pos.aabb(),
+            pos.xa * DT,
+            pos.ya * DT,
+        );
+        pos.x = aabb.x;
+        pos.y = aabb.y;
+        if collisions.left || collisions.right {
+            pos.xa = 0.0;
+        }
+        if collisions.top || collisions.bottom {
+            pos.ya = 0.0;
+        }
+        if collisions.bottom && self.stands_on(&pos, Tile::Bounce) {
+            pos.ya = -BOUNCE_SPEED;
+        }
+        if pos.climbing && !self.touches_tile(&pos, Tile::Ladder) {
+            pos.climbing = false;
+        }
+
+        if pos.climbing {
+            pos.xa *= (-GROUND_FRICTION * DT).exp();
+        } else if !self.on_ground(&pos) {
+            pos.ya += GRAVITY * DT;
+            pos.xa *= (-AIR_DRAG * DT).exp();
         } else {
-            self.player_state.pos.ya -= 0.01;
+            pos.xa *= (-GROUND_FRICTION * DT).exp();
         }
+        pos
     }
 
-    fn can_move(&self, direction: &MoveDirection, entity: &EntityState) -> bool {
-        let pos = &entity.pos;
-        match direction {
-            MoveDirection::Left => {
-                if pos.x as usize <= 0 {
-                    return false;
-                }
-                if let Tile::Full = self.grid[pos.y as usize][(pos.x - 1.0) as usize] {
-                    return false;
+    fn player_controls(&self) -> Controls {
+        let mut controls = Controls::new(1.0);
+        controls.right = self.pressed_keys.contains(&KeyCode::Char('d'));
+        controls.left = self.pressed_keys.contains(&KeyCode::Char('a'));
+        controls.jump = self.pressed_keys.contains(&KeyCode::Char(' '));
+        controls.up = self.pressed_keys.contains(&KeyCode::Char('w'));
+        controls.down = self.pressed_keys.contains(&KeyCode::Char('s'));
+        controls
+    }
+
+    /// Decides how an enemy moves this tick based on its behavior. Returns
+    /// the controls and the direction the enemy faces afterwards.
+    fn enemy_controls(&self, enemy: &Enemy) -> (Controls, MoveDirection) {
+        let pos = &enemy.state.pos;
+        let target = &self.player_state.pos;
+        let towards = if target.x < pos.x {
+            MoveDirection::Left
+        } else {
+            MoveDirection::Right
+        };
+        let distance = (target.x - pos.x).abs();
+        let player_above = target.y < pos.y - 1.0;
+        let on_ground = self.on_ground(pos);
+        let mut controls = Controls::new(enemy.speed);
+        // Keeps holding jump while rising to get the full jump height.
+        controls.jump = pos.ya < 0.0;
+
+        let facing = match enemy.behavior {
+            Behavior::Patrol => {
+                let mut facing = enemy.state.facing;
+                if !self.can_move(&facing, pos) || (on_ground && !self.ground_ahead(pos, facing)) {
+                    facing = facing.opposite();
                 }
-                true
+                controls.walk(facing);
+                facing
             }
-            MoveDirection::Right => {
-                if pos.x as usize >= self.grid_size.width - 1 {
-                    return false;
+            Behavior::Chase => {
+                if distance > 1.0 {
+                    controls.walk(towards);
                 }
-                if let Tile::Full = self.grid[pos.y as usize][(pos.x + 1.0) as usize] {
-                    return false;
+                if on_ground && (!self.can_move(&towards, pos) || player_above) {
+                    controls.jump = true;
                 }
-                true
+                towards
             }
-            MoveDirection::Up => {
-                if pos.y as usize <= 0 {
-                    return false;
+            Behavior::Jumper => {
+                if distance > 1.0 {
+                    controls.walk(towards);
                 }
-                if let Tile::Full = self.grid[(pos.y - 1.0) as usize][pos.x as usize] {
-                    return false;
+                let gap_ahead = !self.ground_ahead(pos, towards);
+                if on_ground && (!self.can_move(&towards, pos) || gap_ahead || player_above) {
+                    controls.jump = true;
                 }
-                true
+                towards
             }
-        }
+            Behavior::KeepDistance(range) => {
+                let range = range as f64;
+                let away = towards.opposite();
+                if distance < range - 2.0 && self.ground_ahead(pos, away) {
+                    controls.walk(away);
+                } else if distance > range + 2.0 {
+                    controls.walk(towards);
+                }
+                towards
+            }
+        };
+        (controls, facing)
     }
 
-    fn step(&mut self, direction: MoveDirection) {
-        if !self.can_move(&direction, &self.player_state) {
-            return;
+    /// Resolves a hit of a rolled `damage` with the same rules as turn based
+    /// battles and knocks the defender away from where the hit came from.
+    /// Returns the damage dealt.
+    fn hit(damage: u128, defender: &mut EntityState, from_x: f64) -> u128 {
+        let health = defender.entity.get_health();
+        Battle::defend(&mut defender.entity, damage);
+        if defender.entity.get_health() < health {
+            defender.pos.xa = if defender.pos.x < from_x {
+                -KNOCKBACK_SPEED
+            } else {
+                KNOCKBACK_SPEED
+            };
+            defender.pos.ya = -KNOCKBACK_LIFT;
         }
-        let mut xaa: f64 = match direction {
-            MoveDirection::Left => -0.1,
-            MoveDirection::Right => 0.1,
-            _ => panic!("Wrong move direction you idiot"),
+        health - defender.entity.get_health()
+    }
+
+    fn shoot(&mut self, shooter: Shooter) {
+        let state = match shooter {
+            Shooter::Player => &self.player_state,
+            Shooter::Enemy(index) => &self.enemies[index].state,
+        };
+        let xa = match state.facing {
+            MoveDirection::Left => -PROJECTILE_SPEED,
+            _ => PROJECTILE_SPEED,
         };
-        if self.on_ground(&self.player_state) {
-            xaa *= 6.0;
+        let offset = (1.0 - PROJECTILE_SIZE) / 2.0;
+        self.projectiles.push(Projectile {
+            x: state.pos.x + offset,
+            y: state.pos.y + offset,
+            xa,
+            shooter,
+        });
+    }
+
+    /// Turns a breakable block into empty space, returns whether there was one.
+    fn break_block(&mut self, x: i64, y: i64) -> bool {
+        if self.tile(x, y) != Some(Tile::Breakable) {
+            return false;
         }
-        self.player_state.pos.xa += xaa;
+        self.grid[y as usize][x as usize] = Tile::Empty;
+        true
     }
 
-    fn tick(&mut self, data: &mut SharedData) {
-        if self.pressed_keys.contains(&KeyCode::Char('d')) {
-            self.step(MoveDirection::Right)
+    /// Breaks the nearest breakable block within melee reach, if no wall is
+    /// in the way.
+    fn break_block_ahead(&mut self, pos: EntityPos, facing: MoveDirection) {
+        let x = (pos.x + 0.5).floor() as i64;
+        let y = (pos.y + 0.5).floor() as i64;
+        let direction = if facing == MoveDirection::Left { -1 } else { 1 };
+        for distance in 1..=MELEE_REACH as i64 {
+            let x = x + distance * direction;
+            if self.break_block(x, y) || self.is_solid(x, y) {
+                return;
+            }
         }
-        if self.pressed_keys.contains(&KeyCode::Char('a')) {
-            self.step(MoveDirection::Left)
+    }
+
+    /// Returns the damage dealt.
+    fn melee(attacker: &mut EntityState, defender: &mut EntityState) -> u128 {
+        attacker.swing = SWING_TIME;
+        if !attacker.reaches(&defender.pos) {
+            return 0;
         }
-        if self.pressed_keys.contains(&KeyCode::Char(' ')) && self.player_state.pos.ya <= 0.0 {
-            self.jump()
+        let damage = Battle::roll_damage(&mut attacker.entity);
+        NBattleScene::hit(damage, defender, attacker.pos.x)
+    }
+
+    /// One swing, rolled once and multiplied by `power`, hits every enemy
+    /// in reach.
+    fn player_swing(&mut self, power: u128) {
+        let player = &mut self.player_state;
+        player.swing = SWING_TIME;
+        let targets: Vec<&mut EntityState> = self
+            .enemies
+            .iter_mut()
+            .map(|enemy| &mut enemy.state)
+            .filter(|state| state.is_alive() && player.reaches(&state.pos))
+            .collect();
+        if !targets.is_empty() {
+            let damage = capped_mul(Battle::roll_damage(&mut player.entity), power);
+            for target in targets {
+                let dealt = NBattleScene::hit(damage, target, player.pos.x);
+                self.damage_dealt = capped_add(self.damage_dealt, dealt);
+            }
         }
+        self.break_block_ahead(self.player_state.pos, self.player_state.facing);
+        self.player_state.cooldown = MELEE_COOLDOWN;
+    }
 
-        self.player_state.pos.xa = self.player_state.pos.xa.clamp(-1.0, 1.0);
-        self.player_state.pos.ya = self.player_state.pos.ya.clamp(-1.0, 1.0);
+    fn player_attacks(&mut self) {
+        if self.player_state.cooldown > 0.0 {
+            return;
+        }
+        if self.pressed_keys.contains(&KeyCode::Char('j')) {
+            self.player_swing(1);
+        } else if self.pressed_keys.contains(&KeyCode::Char('k')) {
+            self.shoot(Shooter::Player);
+            self.player_state.cooldown = RANGED_COOLDOWN;
+        }
+    }
 
-        if self.player_state.pos.xa > 0.0 {
-            if self.can_move(&MoveDirection::Right, &self.player_state) {
-                self.player_state.pos.x += self.player_state.pos.xa;
-            } else {
-                self.player_state.pos.xa = 0.0;
+    fn enemy_attacks(&mut self, index: usize) {
+        let enemy = &self.enemies[index];
+        if !enemy.state.is_alive() || enemy.state.cooldown > 0.0 {
+            return;
+        }
+        let target = &self.player_state.pos;
+        match enemy.attack {
+            AttackKind::Melee if enemy.state.reaches(target) => {
+                let enemy = &mut self.enemies[index].state;
+                NBattleScene::melee(enemy, &mut self.player_state);
+                enemy.cooldown = ENEMY_MELEE_COOLDOWN;
+                let (pos, facing) = (enemy.pos, enemy.facing);
+                self.break_block_ahead(pos, facing);
+            }
+            AttackKind::Ranged if (target.y - enemy.state.pos.y).abs() < 1.0 => {
+                self.shoot(Shooter::Enemy(index));
+                self.enemies[index].state.cooldown = ENEMY_RANGED_COOLDOWN;
             }
+            _ => (),
         }
-        if self.player_state.pos.xa < 0.0 {
-            if self.can_move(&MoveDirection::Left, &self.player_state) {
-                self.player_state.pos.x += self.player_state.pos.xa;
-            } else {
-                self.player_state.pos.xa = 0.0;
+    }
+
+    /// Moves projectiles, dropping the ones that hit a wall or an entity.
+    /// Breakable blocks are destroyed by the projectile that hits them.
+    fn update_projectiles(&mut self) {
+        let mut projectiles = std::mem::take(&mut self.projectiles);
+        let mut hit_walls = vec![];
+        projectiles.retain_mut(|projectile| {
+            let (aabb, collisions) = move_and_collide(
+                |x, y| self.is_solid(x, y),
+                projectile.aabb(),
+                projectile.xa * DT,
+                0.0,
+            );
+            projectile.x = aabb.x;
+            if collisions.any() {
+                let column = if collisions.right {
+                    (aabb.x + aabb.width).round()
+                } else {
+                    aabb.x.round() - 1.0
+                };
+                let wall = Aabb {
+                    x: column,
+                    width: 1.0,
+                    ..aabb
+                };
+                hit_walls.extend(covered_cells(wall));
+                return false;
+            }
+            let hits = |state: &EntityState| {
+                state.is_alive() && state.pos.aabb().intersects(&projectile.aabb())
+            };
+            let (attacker, defender) = match projectile.shooter {
+                Shooter::Player => match self.enemies.iter_mut().find(|enemy| hits(&enemy.state)) {
+                    Some(enemy) => (&mut self.player_state, &mut enemy.state),
+                    None => return true,
+                },
+                Shooter::Enemy(_) if !hits(&self.player_state) => return true,
+                Shooter::Enemy(index) => (&mut self.enemies[index].state, &mut self.player_state),
+            };
+            let from_x = projectile.x - projectile.xa * DT;
+            let damage = Battle::roll_damage(&mut attacker.entity);
+            let damage = NBattleScene::hit(damage, defender, from_x);
+            if projectile.shooter == Shooter::Player {
+                self.damage_dealt = capped_add(self.damage_dealt, damage);
             }
+            false
+        });
+        self.projectiles = projectiles;
+        for (x, y) in hit_walls {
+            self.break_block(x, y);
+        }
+    }
+
+    fn tick(&mut self, data: &mut SharedData) {
+        if !self.player_state.is_alive() || self.alive_enemies().next().is_none() {
+            return;
         }
-        if self.player_state.pos.ya < 0.0 {
-            if self.can_move(&MoveDirection::Up, &self.player_state) {
-                self.player_state.pos.y += self.player_state.pos.ya;
+        let controls = self.player_controls();
+        if controls.left != controls.right {
+            self.player_state.facing = if controls.left {
+                MoveDirection::Left
             } else {
-                self.player_state.pos.ya = 0.0;
+                MoveDirection::Right
+            };
+        }
+        self.player_state.pos = self.move_entity(self.player_state.pos, controls);
+        self.notice_time = (self.notice_time - DT).max(0.0);
+
+        for index in 0..self.enemies.len() {
+            let enemy = &self.enemies[index];
+            if !enemy.state.is_alive() {
+                continue;
             }
+            let (controls, facing) = self.enemy_controls(enemy);
+            let pos = self.move_entity(enemy.state.pos, controls);
+            let state = &mut self.enemies[index].state;
+            state.facing = facing;
+            state.pos = pos;
         }
-        self.player_state.pos.y += self.player_state.pos.ya;
-
-        self.enemy_state.pos.x += self.enemy_state.pos.xa;
-        self.enemy_state.pos.y += self.enemy_state.pos.ya;
-
-        self.player_state.pos.x = self
-            .player_state
-            .pos
-            .x
-            .clamp(0.0, (self.grid_size.width - 1) as f64);
-        self.player_state.pos.y = self
-            .player_state
-            .pos
-            .y
-            .clamp(0.0, (self.grid_size.height - 1) as f64);
-
-        self.enemy_state.pos.x = self
-            .enemy_state
-            .pos
-            .x
-            .clamp(0.0, (self.grid_size.width - 1) as f64);
-        self.enemy_state.pos.y = self
-            .enemy_state
-            .pos
-            .y
-            .clamp(0.0, (self.grid_size.height - 1) as f64);
-
-        if !self.on_ground(&self.player_state) {
-            self.player_state.pos.ya += 0.02;
-            self.player_state.pos.xa *= 0.9;
-        } else {
-            self.player_state.pos.xa /= 20.0;
-            if self.player_state.pos.ya > 0.0 {
-                self.player_state.pos.ya = 0.0;
-                self.player_state.pos.y = (self.player_state.pos.y as u16) as f64 + 0.950;
+
+        let states = [&self.player_state]
+            .into_iter()
+            .chain(self.enemies.iter().map(|enemy| &enemy.state));
+        let on_spikes: Vec<bool> = states
+            .map(|state| self.touches_tile(&state.pos, Tile::Spikes))
+            .collect();
+        let states = [&mut self.player_state]
+            .into_iter()
+            .chain(self.enemies.iter_mut().map(|enemy| &mut enemy.state));
+        for (state, on_spikes) in states.zip(on_spikes) {
+            state.cooldown = (state.cooldown - DT).max(0.0);
+            state.swing = (state.swing - DT).max(0.0);
+            state.spike_cooldown = (state.spike_cooldown - DT).max(0.0);
+            if on_spikes && state.spike_cooldown <= 0.0 && state.is_alive() {
+                state.hurt_by_spikes();
             }
         }
-        if !self.on_ground(&self.enemy_state) {
-            self.enemy_state.pos.ya += 0.04;
-        } else {
-            self.enemy_state.pos.xa /= 20.0;
-            self.enemy_state.pos.ya = 0.0;
+        self.player_attacks();
+        for index in 0..self.enemies.len() {
+            self.enemy_attacks(index);
+        }
+        self.update_projectiles();
+
+        let cleared = self.alive_enemies().next().is_none();
+        let player = &self.player_state.entity;
+        let defeated = self.enemies_defeated();
+        let last_enemy = &self.enemies[self.enemies.len() - 1];
+        // Dying on the same tick as the last enemy is still a defeat.
+        if !self.player_state.is_alive() {
+            let enemy = &self
+                .alive_enemies()
+                .next()
+                .unwrap_or(last_enemy)
+                .state
+                .entity;
+            finish_battle(data, player, enemy, false, self.damage_dealt, defeated);
+        } else if cleared && self.wave < self.waves {
+            self.next_wave();
+        } else if cleared {
+            let enemy = &last_enemy.state.entity;
+            finish_battle(data, player, enemy, true, self.damage_dealt, defeated);
+        }
+    }
+
+    fn alive_enemies(&self) -> impl Iterator<Item = &Enemy> {
+        self.enemies.iter().filter(|enemy| enemy.state.is_alive())
+    }
+
+    fn flee(&mut self, data: &mut SharedData) {
+        sync_after_battle(data, &self.player_state.entity);
+        let message = match self.alive_enemies().next() {
+            Some(enemy) => format!("You fled from {}", enemy.state.entity.get_name()),
+            None => "You fled from the battle".to_string(),
+        };
+        self.message_queue.add_message(message);
+        data.current_scene = StatisticsScene::scene_id();
+    }
+
+    fn notify(&mut self, text: &str) {
+        self.notice = text.into();
+        self.notice_time = NOTICE_TIME;
+    }
+
+    fn select_next_item(&mut self, data: &SharedData) {
+        let items = data.player_data.get_inventory().len();
+        self.selected_item = (self.selected_item + 1) % items.max(1);
+    }
+
+    /// Uses the selected consumable. An escape scroll ends the fight like
+    /// fleeing does.
+    fn use_item(&mut self, data: &mut SharedData) {
+        let inventory = data.player_data.get_mut_inventory();
+        let Some(stack) = inventory.get(self.selected_item) else {
+            self.notify("You have no items to use");
+            return;
+        };
+        let ItemType::Consumable(effect) = stack.item.item_type.clone() else {
+            return;
+        };
+        let player = &mut self.player_state.entity;
+        if matches!(effect, ConsumableEffect::Heal(_))
+            && player.get_health() == player.get_max_health()
+        {
+            self.notify("You are already at full health");
+            return;
+        }
+        inventory.take(self.selected_item);
+        if self.selected_item >= inventory.len() {
+            self.selected_item = 0;
+        }
+        match effect {
+            ConsumableEffect::Escape => self.flee(data),
+            _ => player.use_consumable(&effect),
+        }
+    }
+
+    /// Unlocked active skills that weren't used during this battle yet.
+    fn available_skills(&self, data: &SharedData) -> Vec<ActiveSkill> {
+        data.player_data
+            .active_skills()
+            .into_iter()
+            .filter(|skill| !self.used_skills.contains(skill))
+            .collect()
+    }
+
+    fn select_next_skill(&mut self, data: &SharedData) {
+        let skills = self.available_skills(data).len();
+        self.selected_skill = (self.selected_skill + 1) % skills.max(1);
+    }
+
+    /// Uses the selected skill, each one works once per battle.
+    fn use_skill(&mut self, data: &SharedData) {
+        let Some(skill) = self
+            .available_skills(data)
+            .get(self.selected_skill)
+            .copied()
+        else {
+            self.notify("You have no skills to use");
+            return;
+        };
+        self.used_skills.push(skill);
+        self.selected_skill = 0;
+        match skill {
+            ActiveSkill::PowerStrike => self.player_swing(2),
+            ActiveSkill::SecondWind => {
+                let player = &mut self.player_state.entity;
+                player.heal(player.get_max_health() / 3);
+            }
         }
     }
 
@@ -341,12 +1173,19 @@ impl Scene for NBattleScene {
 
     fn set_message_queue(&mut self, queue: MessageQueue) {
         self.message_queue = queue;
+        for error in self.errors.drain(..) {
+            self.message_queue.add_message(error);
+        }
     }
 
     fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
         if key.kind == KeyEventKind::Press {
             match key.code {
-                KeyCode::Enter => data.current_scene = StatisticsScene::scene_id(),
+                KeyCode::Esc => self.flee(data),
+                KeyCode::Char('q') => self.select_next_item(data),
+                KeyCode::Char('e') => self.use_item(data),
+                KeyCode::Char('r') => self.select_next_skill(data),
+                KeyCode::Char('f') => self.use_skill(data),
                 KeyCode::Char(pressed_key) => self.key_down(key.code),
                 _ => (),
             }
@@ -355,20 +1194,128 @@ impl Scene for NBattleScene {
         }
     }
 
-    fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
-        if frame.size().width < self.grid_size.width as u16
-            || frame.size().height < self.grid_size.height as u16
-        {
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
+        if frame.size().height < 3 {
             let p = Paragraph::new("Terminal is too small");
             let area = get_full_size_rect(frame);
             frame.render_widget(p, area);
             return;
         }
-        self.render_grid(frame);
-        self.render_entities(frame);
+        let camera = Camera::new(frame.size(), &self.grid_size, &self.player_state.pos);
+        self.render_grid(frame, &camera);
+        self.render_entities(frame, &camera);
+        self.render_status(frame, &camera, data);
     }
 
     fn update(&mut self, data: &mut SharedData) {
+        // An open message takes every key, releases included, so the fight
+        // waits for it to close and forgets what was held.
+        if self.message_queue.get_message().is_some() {
+            self.pressed_keys.clear();
+            return;
+        }
         self.tick(data);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::Player;
+
+    fn pos(x: f64, y: f64) -> EntityPos {
+        EntityPos {
+            x,
+            y,
+            xa: 0.0,
+            ya: 0.0,
+            climbing: false,
+        }
+    }
+
+    fn scene(waves: u32) -> NBattleScene {
+        let mut data = SharedData::new(Player::default(), NBattleScene::scene_id());
+        let setup = BattleSetup {
+            seed: 7,
+            arena: None,
+            waves,
+        };
+        data.scene_data_transfer = Some(serde_json::to_string(&setup).unwrap());
+        NBattleScene::new(&data)
+    }
+
+    fn projectile(shooter: Shooter) -> Projectile {
+        Projectile {
+            x: 1.0,
+            y: 1.0,
+            xa: 1.0,
+            shooter,
+        }
+    }
+
+    #[test]
+    fn waves_grow_up_to_the_cap_and_spawns() {
+        let mut scene = scene(10);
+        for wave in 1..=5 {
+            assert_eq!(scene.wave, wave as u32);
+            let expected = wave.min(MAX_WAVE_SIZE).min(scene.enemy_spawns.len());
+            assert_eq!(scene.enemies.len(), expected);
+            scene.next_wave();
+        }
+        scene.enemy_spawns.truncate(2);
+        scene.next_wave();
+        assert_eq!(scene.enemies.len(), 2);
+        let (first, second) = (&scene.enemies[0].state.pos, &scene.enemies[1].state.pos);
+        assert!(first.x != second.x || first.y != second.y);
+    }
+
+    #[test]
+    fn waves_keep_player_shots_and_count_defeats() {
+        let mut scene = scene(3);
+        assert_eq!(scene.enemies_defeated(), 0);
+        scene.projectiles = vec![projectile(Shooter::Player), projectile(Shooter::Enemy(0))];
+        for enemy in &mut scene.enemies {
+            enemy.state.entity.take_damage(u128::MAX);
+        }
+        assert_eq!(scene.enemies_defeated(), 1);
+        scene.next_wave();
+        assert_eq!(scene.projectiles.len(), 1);
+        assert!(scene.projectiles[0].shooter == Shooter::Player);
+        assert_eq!(scene.enemies_defeated(), 1);
+        let second_wave = scene.enemies.len() as u128;
+        scene.enemies[0].state.entity.take_damage(u128::MAX);
+        scene.next_wave();
+        assert_eq!(scene.enemies_defeated(), 1 + second_wave);
+    }
+
+    #[test]
+    fn small_arenas_are_centred() {
+        assert_eq!(Camera::axis(80, 40, 10.0), (0, 20, 40));
+        assert_eq!(Camera::axis(81, 40, 39.0), (0, 20, 40));
+        assert_eq!(Camera::axis(40, 40, 0.0), (0, 0, 40));
+    }
+
+    #[test]
+    fn camera_clamps_at_arena_edges() {
+        assert_eq!(Camera::axis(20, 100, 0.0), (0, 0, 20));
+        assert_eq!(Camera::axis(20, 100, 99.0), (80, 0, 20));
+        let (first, _, _) = Camera::axis(20, 100, 50.0);
+        assert!((40..=41).contains(&first));
+    }
+
+    #[test]
+    fn resizing_keeps_the_target_in_view() {
+        let grid_size = GridSize {
+            width: 120,
+            height: 30,
+        };
+        let target = pos(90.0, 20.0);
+        for (width, height) in [(200, 50), (60, 20), (7, 5), (121, 32)] {
+            let camera = Camera::new(Rect::new(0, 0, width, height), &grid_size, &target);
+            assert!(camera.area.width as usize <= grid_size.width);
+            assert!(camera.area.height as usize <= grid_size.height);
+            assert!(camera.area.right() <= width && camera.area.bottom() < height);
+            assert!(camera.to_screen(target.x, target.y).is_some());
+        }
+    }
+}
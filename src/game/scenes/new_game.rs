@@ -0,0 +1,465 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::{
+    class::Class,
+    difficulty::Difficulty,
+    message_queue::MessageQueue,
+    player::{Player, MAX_NAME_LENGTH},
+    ui::text_input::TextInput,
+    utils::{load_save, write_save, SAVE_SLOTS},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use rand::Rng;
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 8;
+/// Digits in the largest seed, so every seed shown in the game can be typed back.
+pub const MAX_SEED_LENGTH: usize = u64::MAX.ilog10() as usize + 1;
+
+/// `None` for an empty prompt, which means a random seed.
+pub fn parse_seed(text: &str) -> Result<Option<u64>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match text.parse() {
+        Ok(seed) => Ok(Some(seed)),
+        Err(_) => Err(format!("The seed can't be larger than {}", u64::MAX)),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Step {
+    Slot,
+    Name,
+    Class,
+    Difficulty,
+    Seed,
+    Confirm,
+}
+
+impl Step {
+    fn previous(&self) -> Step {
+        match self {
+            Step::Slot | Step::Name => Step::Slot,
+            Step::Class => Step::Name,
+            Step::Difficulty => Step::Class,
+            Step::Seed => Step::Difficulty,
+            Step::Confirm => Step::Seed,
+        }
+    }
+}
+
+/// Summary of a saved character shown when picking a slot.
+struct SlotInfo {
+    name: String,
+    class: Class,
+    level: u128,
+}
+
+enum SaveSlot {
+    Empty,
+    Saved(SlotInfo),
+    /// The file is there but can't be read, it can only be overwritten.
+    Corrupted,
+}
+
+pub struct NewGameScene {
+    step: Step,
+    slots: Vec<SaveSlot>,
+    slot_id: usize,
+    load_slot: bool,
+    name: TextInput,
+    name_error: Option<&'static str>,
+    seed_error: Option<String>,
+    class_id: usize,
+    difficulty_id: usize,
+    hardcore: bool,
+    seed: TextInput,
+    message_queue: MessageQueue,
+}
+
+impl NewGameScene {
+    pub fn new() -> Self {
+        let slots = (1..=SAVE_SLOTS)
+            .map(|slot| match load_save(slot) {
+                Ok(Some(player)) => SaveSlot::Saved(SlotInfo {
+                    name: player.get_name().into(),
+                    class: player.get_class(),
+                    level: player.get_level(),
+                }),
+                Ok(None) => SaveSlot::Empty,
+                Err(_) => SaveSlot::Corrupted,
+            })
+            .collect();
+        NewGameScene {
+            step: Step::Slot,
+            slots,
+            slot_id: 0,
+            load_slot: true,
+            name: TextInput::new(MAX_NAME_LENGTH).with_charset(Player::is_name_grapheme),
+            name_error: None,
+            seed_error: None,
+            class_id: 0,
+            difficulty_id: 1,
+            hardcore: false,
+            seed: TextInput::new(MAX_SEED_LENGTH)
+                .with_charset(|grapheme| grapheme.chars().all(|ch| ch.is_ascii_digit())),
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+
+    fn slot(&self) -> u8 {
+        self.slot_id as u8 + 1
+    }
+
+    fn slot_taken(&self) -> bool {
+        matches!(self.slots[self.slot_id], SaveSlot::Saved(_))
+    }
+
+    fn load_game(&mut self, data: &mut SharedData) {
+        match load_save(self.slot()) {
+            Ok(Some(saved_data)) => {
+                let msg_queue = data.player_data.get_message_queue();
+                data.player_data = saved_data;
+                data.player_data.set_message_queue(msg_queue);
+                data.current_scene = StatisticsScene::scene_id();
+            }
+            Ok(None) => (),
+            Err(err) => self.message_queue.add_message(err),
+        }
+    }
+
+    fn create_player(&mut self, data: &mut SharedData) {
+        let seed = match parse_seed(self.seed.value()) {
+            Ok(Some(seed)) => seed,
+            _ => rand::thread_rng().gen(),
+        };
+        let mut player = Player::default();
+        player.set_message_queue(data.player_data.get_message_queue());
+        player.set_name(self.name.value().trim().into());
+        player.choose_class(Class::ALL[self.class_id]);
+        player.set_difficulty(Difficulty::ALL[self.difficulty_id]);
+        player.set_hardcore(self.hardcore);
+        player.set_seed(seed);
+        player.set_save_slot(self.slot());
+        write_save(&player);
+        data.player_data = player;
+        data.current_scene = StatisticsScene::scene_id();
+    }
+
+    fn handle_step_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        match self.step {
+            Step::Slot => match key.code {
+                KeyCode::Up if self.slot_id > 0 => self.slot_id -= 1,
+                KeyCode::Down if self.slot_id + 1 < self.slots.len() => self.slot_id += 1,
+                KeyCode::Left | KeyCode::Right => self.load_slot = !self.load_slot,
+                KeyCode::Enter => {
+                    if self.slot_taken() && self.load_slot {
+                        self.load_game(data);
+                    } else {
+                        self.step = Step::Name;
+                    }
+                }
+                _ => (),
+            },
+            Step::Name => match key.code {
+                _ if self.name.handle_key(&key) => (),
+                KeyCode::Enter => match Player::validate_name(self.name.value()) {
+                    Ok(()) => {
+                        self.name_error = None;
+                        self.step = Step::Class;
+                    }
+                    Err(err) => self.name_error = Some(err),
+                },
+                _ => (),
+            },
+            Step::Class => match key.code {
+                KeyCode::Up if self.class_id > 0 => self.class_id -= 1,
+                KeyCode::Down if self.class_id + 1 < Class::ALL.len() => self.class_id += 1,
+                KeyCode::Enter => self.step = Step::Difficulty,
+                _ => (),
+            },
+            Step::Difficulty => match key.code {
+                KeyCode::Up if self.difficulty_id > 0 => self.difficulty_id -= 1,
+                KeyCode::Down if self.difficulty_id < Difficulty::ALL.len() => {
+                    self.difficulty_id += 1
+                }
+                KeyCode::Enter if self.difficulty_id == Difficulty::ALL.len() => {
+                    self.hardcore = !self.hardcore
+                }
+                KeyCode::Enter => self.step = Step::Seed,
+                _ => (),
+            },
+            Step::Seed => match key.code {
+                _ if self.seed.handle_key(&key) => (),
+                KeyCode::Enter => match parse_seed(self.seed.value()) {
+                    Ok(_) => {
+                        self.seed_error = None;
+                        self.step = Step::Confirm;
+                    }
+                    Err(err) => self.seed_error = Some(err),
+                },
+                _ => (),
+            },
+            Step::Confirm => {
+                if key.code == KeyCode::Enter {
+                    self.create_player(data)
+                }
+            }
+        }
+    }
+
+    fn render_slots(&self, lines: &mut Vec<Line<'static>>) {
+        lines.push(Line::from("Choose a save slot".bold()));
+        lines.push(Line::from(""));
+        for (index, slot) in self.slots.iter().enumerate() {
+            let style = if index == self.slot_id {
+                Style::default().bg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            let info = match slot {
+                SaveSlot::Saved(info) => {
+                    format!("{}, level {} {}", info.name, info.level, info.class.name())
+                }
+                SaveSlot::Empty => "Empty".into(),
+                SaveSlot::Corrupted => "Corrupted save".into(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("Slot {}", index + 1), style),
+                Span::raw(format!(" | {}", info)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        if self.slot_taken() {
+            let highlighted = Style::default().on_cyan();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Load",
+                    if self.load_slot {
+                        highlighted
+                    } else {
+                        Style::default()
+                    },
+                ),
+                Span::raw(" / "),
+                Span::styled(
+                    "New game (overwrites the slot)",
+                    if !self.load_slot {
+                        highlighted
+                    } else {
+                        Style::default()
+                    },
+                ),
+            ]));
+        } else if let SaveSlot::Corrupted = self.slots[self.slot_id] {
+            lines.push(Line::from(
+                "The save can't be read, press Enter to start a new game over it"
+                    .fg(Color::LightRed),
+            ));
+        } else {
+            lines.push(Line::from("Press Enter to start a new game"));
+        }
+    }
+
+    fn render_name(&self, lines: &mut Vec<Line<'static>>, width: usize) {
+        lines.push(Line::from("Enter your nickname".bold()));
+        lines.push(Line::from(""));
+        lines.push(self.name.line(width, Style::default()));
+        if let Some(err) = self.name_error {
+            lines.push(Line::from(err.fg(Color::LightRed)));
+        }
+    }
+
+    fn render_class(&self, lines: &mut Vec<Line<'static>>) {
+        lines.push(Line::from("Choose your class".bold()));
+        lines.push(Line::from(""));
+        for (index, class) in Class::ALL.iter().enumerate() {
+            let style = if index == self.class_id {
+                Style::default().bg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(class.name(), style),
+                Span::styled(
+                    format!(" - {}", class.description()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        let class = Class::ALL[self.class_id];
+        let (health, damage) = class.stats_from_level(1);
+        let gear = class
+            .starting_gear()
+            .iter()
+            .map(|item| item.name.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Health | "),
+            Span::styled(health.to_string(), Style::default().bold().green()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::raw("Damage | "),
+            Span::styled(
+                damage.to_string(),
+                Style::default().bold().fg(Color::LightRed),
+            ),
+        ]));
+        lines.push(Line::from(format!("Gear   | {}", gear)));
+        for skill in class.skills() {
+            lines.push(Line::from(vec![
+                Span::raw("Skill  | "),
+                Span::styled(skill.name, Style::default().bold()),
+                Span::styled(
+                    format!(" - {} (level {})", skill.description, skill.unlock_level),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
+    fn render_difficulty(&self, lines: &mut Vec<Line<'static>>) {
+        lines.push(Line::from("Choose the difficulty".bold()));
+        lines.push(Line::from(""));
+        for (index, difficulty) in Difficulty::ALL.iter().enumerate() {
+            let style = if index == self.difficulty_id {
+                Style::default().bg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(difficulty.name(), style),
+                Span::styled(
+                    format!(" - {}", difficulty.description()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        let hardcore_style = if self.difficulty_id == Difficulty::ALL.len() {
+            Style::default().bg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("Hardcore: {}", if self.hardcore { "On" } else { "Off" }),
+                hardcore_style,
+            ),
+            Span::styled(
+                " - death deletes the save for good",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    fn render_seed(&self, lines: &mut Vec<Line<'static>>, width: usize) {
+        lines.push(Line::from("Enter a seed (optional)".bold()));
+        lines.push(Line::from(
+            "Same seed gives the same battles, leave empty for a random one".fg(Color::DarkGray),
+        ));
+        lines.push(Line::from(""));
+        lines.push(self.seed.line(width, Style::default()));
+        if let Some(err) = &self.seed_error {
+            let style = Style::default().fg(Color::LightRed);
+            lines.push(Line::from(Span::styled(err.clone(), style)));
+        }
+    }
+
+    fn render_confirm(&self, lines: &mut Vec<Line<'static>>) {
+        let seed = if self.seed.is_empty() {
+            "Random".to_string()
+        } else {
+            self.seed.value().to_string()
+        };
+        lines.push(Line::from("Ready to start?".bold()));
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Slot       | {}", self.slot())));
+        lines.push(Line::from(format!(
+            "Name       | {}",
+            self.name.value().trim()
+        )));
+        lines.push(Line::from(format!(
+            "Class      | {}",
+            Class::ALL[self.class_id].name()
+        )));
+        lines.push(Line::from(format!(
+            "Difficulty | {}{}",
+            Difficulty::ALL[self.difficulty_id.min(Difficulty::ALL.len() - 1)].name(),
+            if self.hardcore { ", hardcore" } else { "" }
+        )));
+        lines.push(Line::from(format!("Seed       | {}", seed)));
+        lines.push(Line::from(""));
+        lines.push(Line::from("Press Enter to begin your journey"));
+    }
+}
+
+impl Scene for NewGameScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if key.code == KeyCode::Esc {
+            self.step = self.step.previous();
+            return;
+        }
+        self.handle_step_input(key, data);
+    }
+
+    fn handle_paste(&mut self, text: &str, _: &mut SharedData) {
+        match self.step {
+            Step::Name => self.name.insert_str(text),
+            Step::Seed => self.seed.insert_str(text),
+            _ => (),
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
+        let width = frame.size().width as usize - 1;
+        let mut lines = vec![];
+        match self.step {
+            Step::Slot => self.render_slots(&mut lines),
+            Step::Name => self.render_name(&mut lines, width),
+            Step::Class => self.render_class(&mut lines),
+            Step::Difficulty => self.render_difficulty(&mut lines),
+            Step::Seed => self.render_seed(&mut lines, width),
+            Step::Confirm => self.render_confirm(&mut lines),
+        }
+        if self.step != Step::Slot {
+            lines.push(Line::from(""));
+            lines.push(Line::from(
+                "Enter to continue, Esc to go back".fg(Color::DarkGray),
+            ));
+        }
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
@@ -1,7 +1,7 @@
 use super::{stats::StatisticsScene, Scene, SharedData};
 use crate::game::{
-    item::{Item, ItemProperties, ItemType},
-    message_queue::MessageQueue,
+    achievements::GameEvent, item::Item, message_queue::MessageQueue, numeric::capped_add,
+    shop::shop_items,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
@@ -19,6 +19,7 @@ const SCENE_ID: i32 = 3;
 enum Stage {
     ItemSelecting,
     ConfirmBuy(bool),
+    ConfirmRepair(bool),
 }
 
 pub struct ShopScene {
@@ -30,26 +31,8 @@ pub struct ShopScene {
 
 impl ShopScene {
     pub fn new() -> Self {
-        let sword = Item {
-            name: "Sample Sword".into(),
-            item_type: ItemType::Sword,
-            cost: 10,
-            properties: ItemProperties {
-                damage: 10,
-                defence: 0,
-            },
-        };
-        let shield = Item {
-            name: "Sample Shield".into(),
-            item_type: ItemType::Shield,
-            cost: 10,
-            properties: ItemProperties {
-                damage: 0,
-                defence: 4,
-            },
-        };
         ShopScene {
-            items: vec![sword, shield],
+            items: shop_items(),
             selected_id: 0,
             stage: Stage::ItemSelecting,
             message_queue: MessageQueue::default(),
@@ -60,6 +43,11 @@ impl ShopScene {
         SCENE_ID
     }
 
+    /// The repair service sits right after the last item in the list.
+    fn repair_id(&self) -> u8 {
+        self.items.len() as u8 + 1
+    }
+
     fn buy_item(&mut self, data: &mut SharedData) {
         let item = self.items.get((self.selected_id - 1) as usize).unwrap();
         if data.player_data.get_coins() < item.cost as u128 {
@@ -67,15 +55,80 @@ impl ShopScene {
             self.message_queue.add_message(msg);
             return;
         }
-        data.player_data.remove_coins(item.cost as u128);
-        let equip = data.player_data.get_mut_equipment();
-        match item.item_type {
-            ItemType::Sword => equip.sword = Some(item.clone()),
-            ItemType::Shield => equip.shield = Some(item.clone()),
-        }
+        data.player_data.spend_coins(item.cost as u128);
+        data.player_data.receive_item(item.clone());
+        data.player_data
+            .record_event(GameEvent::ItemBought(&item.name));
+        let stats = data.player_data.get_mut_lifetime_stats();
+        stats.items_bought = capped_add(stats.items_bought, 1);
         let msg = format!("Succesfully bought {}!", &item.name);
         self.message_queue.add_message(msg);
     }
+
+    fn repair_equipment(&mut self, data: &mut SharedData) {
+        let cost = data.player_data.get_equipment().repair_cost();
+        if cost == 0 {
+            self.message_queue
+                .add_message("Your equipment doesn't need any repairs".into());
+            return;
+        }
+        if data.player_data.get_coins() < cost {
+            let msg = format!("Not enough coins to repair your equipment ({}c)", cost);
+            self.message_queue.add_message(msg);
+            return;
+        }
+        data.player_data.spend_coins(cost);
+        data.player_data.get_mut_equipment().repair();
+        self.message_queue
+            .add_message("Your equipment is as good as new!".into());
+    }
+
+    fn render_confirmation(
+        &self,
+        frame: &mut crate::Frame,
+        title: &str,
+        text: &[String],
+        confirmed: bool,
+    ) {
+        let length = text.iter().map(|line| line.len()).max().unwrap_or(0) + 2;
+        let paragraph = Paragraph::new(text.join("\n")).bold();
+        let area = Rect {
+            x: frame.size().width / 2 - length as u16 / 2,
+            y: frame.size().height / 2 - 2,
+            width: length as u16,
+            height: text.len() as u16 + 2,
+        };
+        let highlighted = Style::default().on_cyan();
+        let confirmation = Line::from(vec![
+            Span::styled(
+                "Yes",
+                if confirmed {
+                    highlighted
+                } else {
+                    Style::default()
+                },
+            ),
+            Span::raw(" / "),
+            Span::styled(
+                "No",
+                if !confirmed {
+                    highlighted
+                } else {
+                    Style::default()
+                },
+            ),
+        ]);
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(
+                Title::from(confirmation)
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Right),
+            )
+            .title(Title::from(title).alignment(Alignment::Right));
+        frame.render_widget(paragraph.block(block), area);
+    }
 }
 
 impl Scene for ShopScene {
@@ -93,17 +146,15 @@ impl Scene for ShopScene {
         }
         match self.stage {
             Stage::ItemSelecting => match key.code {
-                KeyCode::Up => {
-                    if self.selected_id > 0 {
-                        self.selected_id -= 1
-                    }
-                }
+                KeyCode::Up if self.selected_id > 0 => self.selected_id -= 1,
                 KeyCode::Down => {
-                    self.selected_id = std::cmp::min(self.items.len() as u8, self.selected_id + 1)
+                    self.selected_id = std::cmp::min(self.repair_id(), self.selected_id + 1)
                 }
                 KeyCode::Enter => {
                     if self.selected_id == 0 {
                         data.current_scene = StatisticsScene::scene_id()
+                    } else if self.selected_id == self.repair_id() {
+                        self.stage = Stage::ConfirmRepair(true)
                     } else {
                         self.stage = Stage::ConfirmBuy(true)
                     }
@@ -121,10 +172,21 @@ impl Scene for ShopScene {
                 }
                 _ => (),
             },
+            Stage::ConfirmRepair(confirmed) => match key.code {
+                KeyCode::Right => self.stage = Stage::ConfirmRepair(false),
+                KeyCode::Left => self.stage = Stage::ConfirmRepair(true),
+                KeyCode::Enter => {
+                    if confirmed {
+                        self.repair_equipment(data)
+                    }
+                    self.stage = Stage::ItemSelecting
+                }
+                _ => (),
+            },
         }
     }
 
-    fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
         let area = Rect {
             x: 0,
             y: 0,
@@ -151,6 +213,16 @@ impl Scene for ShopScene {
             );
             shop_list.push(Line::from(content))
         }
+        let repair_cost = data.player_data.get_equipment().repair_cost();
+        let mut style = Style::default();
+        if self.selected_id == self.repair_id() {
+            style = style.bg(Color::Cyan);
+        }
+        shop_list.push(Line::from(""));
+        shop_list.push(Line::from(Span::styled(
+            format!("Repair equipment - {} c.", repair_cost),
+            style,
+        )));
         let area = Rect {
             x: 0,
             y: 2,
@@ -160,49 +232,23 @@ impl Scene for ShopScene {
         let shop_list = Paragraph::new(shop_list);
         frame.render_widget(shop_list, area);
 
-        if let Stage::ConfirmBuy(confirmed) = self.stage {
-            let item = self.items.get((self.selected_id - 1) as usize).unwrap();
-            let warning = "You sure you want to buy";
-            let item_info = format!("{} for {}c?", &item.name, item.cost);
-            let length = std::cmp::max(warning.len(), item_info.len()) + 2;
-            let paragraph = Paragraph::new(format!("{}\n{}", warning, item_info)).bold();
-            let title = "Confirm buy";
-            let area = Rect {
-                x: frame.size().width / 2 - length as u16 / 2,
-                y: frame.size().height / 2 - 2,
-                width: length as u16,
-                height: 4,
-            };
-            let highlighted = Style::default().on_cyan();
-            let confirmation = Line::from(vec![
-                Span::styled(
-                    "Yes",
-                    if confirmed {
-                        highlighted
-                    } else {
-                        Style::default()
-                    },
-                ),
-                Span::raw(" / "),
-                Span::styled(
-                    "No",
-                    if !confirmed {
-                        highlighted
-                    } else {
-                        Style::default()
-                    },
-                ),
-            ]);
-            let block = Block::new()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .title(
-                    Title::from(confirmation)
-                        .position(Position::Bottom)
-                        .alignment(Alignment::Right),
-                )
-                .title(Title::from(title.clone()).alignment(Alignment::Right));
-            frame.render_widget(paragraph.clone().block(block), area);
+        match self.stage {
+            Stage::ConfirmBuy(confirmed) => {
+                let item = self.items.get((self.selected_id - 1) as usize).unwrap();
+                let text = [
+                    "You sure you want to buy".to_string(),
+                    format!("{} for {}c?", &item.name, item.cost),
+                ];
+                self.render_confirmation(frame, "Confirm buy", &text, confirmed);
+            }
+            Stage::ConfirmRepair(confirmed) => {
+                let text = [
+                    "You sure you want to repair".to_string(),
+                    format!("your equipment for {}c?", repair_cost),
+                ];
+                self.render_confirmation(frame, "Confirm repair", &text, confirmed);
+            }
+            Stage::ItemSelecting => (),
         }
     }
 
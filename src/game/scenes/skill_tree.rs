@@ -0,0 +1,160 @@
+use super::{stats::StatisticsScene, Scene, SharedData};
+use crate::game::{
+    message_queue::MessageQueue,
+    skill_tree::{find_node, tier_nodes, tiers_count, SkillKind, SkillNode},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    prelude::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const SCENE_ID: i32 = 9;
+
+pub struct SkillTreeScene {
+    tier: usize,
+    column: usize,
+    message_queue: MessageQueue,
+}
+
+impl SkillTreeScene {
+    pub fn new() -> Self {
+        SkillTreeScene {
+            tier: 0,
+            column: 0,
+            message_queue: MessageQueue::default(),
+        }
+    }
+
+    pub fn scene_id() -> i32 {
+        SCENE_ID
+    }
+
+    fn selected_node(&self) -> &'static SkillNode {
+        tier_nodes(self.tier)[self.column]
+    }
+
+    fn node_style(&self, node: &SkillNode, data: &SharedData) -> Style {
+        let style = if data.player_data.has_skill(node.id) {
+            Style::default().bold().fg(Color::LightGreen)
+        } else if data.player_data.can_unlock_skill(node.id).is_ok() {
+            Style::default().bold().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        if node.id == self.selected_node().id {
+            style.bg(Color::Cyan)
+        } else {
+            style
+        }
+    }
+}
+
+impl Scene for SkillTreeScene {
+    fn scene_id(&self) -> i32 {
+        SCENE_ID
+    }
+
+    fn set_message_queue(&mut self, queue: MessageQueue) {
+        self.message_queue = queue;
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        match key.code {
+            KeyCode::Up if self.tier > 0 => {
+                self.tier -= 1;
+                self.column = self.column.min(tier_nodes(self.tier).len() - 1);
+            }
+            KeyCode::Down if self.tier + 1 < tiers_count() => {
+                self.tier += 1;
+                self.column = self.column.min(tier_nodes(self.tier).len() - 1);
+            }
+            KeyCode::Left if self.column > 0 => self.column -= 1,
+            KeyCode::Right if self.column + 1 < tier_nodes(self.tier).len() => self.column += 1,
+            KeyCode::Enter => {
+                let node = self.selected_node();
+                let msg = match data.player_data.unlock_skill(node.id) {
+                    Ok(()) => format!("Unlocked {}!", node.name),
+                    Err(err) => err.into(),
+                };
+                self.message_queue.add_message(msg);
+            }
+            KeyCode::Esc => data.current_scene = StatisticsScene::scene_id(),
+            _ => (),
+        }
+    }
+
+    fn render(&self, frame: &mut crate::Frame, data: &SharedData) {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw("Skill points | "),
+                Span::styled(
+                    data.player_data.get_skill_points().to_string(),
+                    Style::default().bold().fg(Color::Yellow),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        for tier in 0..tiers_count() {
+            if tier > 0 {
+                lines.push(Line::from("   │".fg(Color::DarkGray)));
+            }
+            let mut spans = vec![Span::styled(
+                format!("{} ", tier + 1),
+                Style::default().fg(Color::DarkGray),
+            )];
+            for (index, node) in tier_nodes(tier).into_iter().enumerate() {
+                if index > 0 {
+                    spans.push(Span::styled(" ── ", Style::default().fg(Color::DarkGray)));
+                }
+                spans.push(Span::styled(
+                    format!("[{}]", node.name),
+                    self.node_style(node, data),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let node = self.selected_node();
+        let kind = match node.kind {
+            SkillKind::Passive(_) => "Passive",
+            SkillKind::Active(_) => "Active",
+        };
+        let prerequisites = node
+            .prerequisites
+            .iter()
+            .filter_map(|id| find_node(id))
+            .map(|node| node.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(node.name, Style::default().bold()),
+            Span::styled(format!(" ({})", kind), Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.push(Line::from(node.description));
+        lines.push(Line::from(format!("Cost     | {} points", node.cost)));
+        if !prerequisites.is_empty() {
+            lines.push(Line::from(format!("Requires | {}", prerequisites)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            "Arrows to move, Enter to unlock, Esc to go back".fg(Color::DarkGray),
+        ));
+
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: frame.size().width - 1,
+            height: frame.size().height - 1,
+        };
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn update(&mut self, _: &mut SharedData) {}
+}
@@ -1,8 +1,11 @@
 use super::{
-    new_battle::NBattleScene, shop::ShopScene, username::UsernameScene, Scene, SharedData,
+    achievements::AchievementsScene, arena_select::ArenaSelectScene, attributes::AttributesScene,
+    crafting::CraftingScene, hall_of_fame::HallOfFameScene, inventory::InventoryScene,
+    shop::ShopScene, skill_tree::SkillTreeScene, username::UsernameScene, Scene, SharedData,
 };
 use crate::{
     game::{
+        item::Item,
         message_queue::MessageQueue,
         utils::{calculate_bar, load_save, write_save},
     },
@@ -18,16 +21,40 @@ use ratatui::{
 
 const SCENE_ID: i32 = 1;
 
+/// What is shown below the menu.
+#[derive(PartialEq)]
+enum Page {
+    Equipment,
+    Lifetime,
+}
+
 pub struct StatisticsScene {
     choosen_text_id: i32,
-    texts: [&'static str; 6],
+    page: Page,
+    texts: [&'static str; 14],
     message_queue: MessageQueue,
 }
 impl StatisticsScene {
     pub fn new() -> Self {
         StatisticsScene {
             choosen_text_id: 0,
-            texts: ["Battle", "Shop", "Change nickname", "Save", "Load", "Exit"],
+            page: Page::Equipment,
+            texts: [
+                "Battle",
+                "Shop",
+                "Craft",
+                "Inventory",
+                "Attributes",
+                "Skills",
+                "Rest",
+                "Change nickname",
+                "Hall of fame",
+                "Achievements",
+                "Statistics",
+                "Save",
+                "Load",
+                "Exit",
+            ],
             message_queue: MessageQueue::default(),
         }
     }
@@ -37,6 +64,49 @@ impl StatisticsScene {
     }
 }
 
+fn item_spans(item: &Option<Item>) -> Vec<Span<'static>> {
+    let Some(item) = item else {
+        return vec![];
+    };
+    let durability_style = if item.is_broken() {
+        Style::default().bold().fg(Color::LightRed)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    vec![
+        Span::raw(item.display_name()),
+        Span::styled(
+            format!(" ({}/{})", item.durability, item.max_durability),
+            durability_style,
+        ),
+    ]
+}
+
+fn lifetime_lines(data: &SharedData) -> Vec<Line<'static>> {
+    let stats = data.player_data.get_lifetime_stats();
+    let rows = [
+        ("Battles fought", stats.battles_fought().to_string()),
+        ("Battles won", stats.battles_won.to_string()),
+        ("Battles lost", stats.battles_lost.to_string()),
+        ("Damage dealt", stats.damage_dealt.to_string()),
+        ("Coins earned", stats.coins_earned.to_string()),
+        ("Coins spent", stats.coins_spent.to_string()),
+        ("Items bought", stats.items_bought.to_string()),
+        ("Play time", stats.play_time()),
+    ];
+    let mut lines = vec![Line::from("       | Lifetime statistics")];
+    for (name, value) in rows {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<15}| ", name),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(value, Style::default().bold()),
+        ]));
+    }
+    lines
+}
+
 impl Scene for StatisticsScene {
     fn scene_id(&self) -> i32 {
         SCENE_ID
@@ -49,12 +119,46 @@ impl Scene for StatisticsScene {
     fn render(&self, frame: &mut Frame, data: &SharedData) {
         let empty = Line::from("");
         let playername = Line::from(format!("Name   | {}", data.player_data.get_name()));
+        let class = Line::from(vec![
+            Span::raw("Class  | "),
+            Span::styled(
+                data.player_data.get_class().name(),
+                Style::default().bold().fg(Color::LightBlue),
+            ),
+        ]);
+        let difficulty = data.player_data.get_difficulty();
+        let mode = Line::from(vec![
+            Span::raw("Mode   | "),
+            Span::styled(
+                if data.player_data.is_hardcore() {
+                    format!("{}, hardcore", difficulty.name())
+                } else {
+                    difficulty.name().to_string()
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!(" (seed {})", data.player_data.get_seed()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+        let (filled, missing) = calculate_bar(
+            data.player_data.get_current_health(),
+            data.player_data.get_max_health(),
+            10,
+        );
         let health = Line::from(vec![
             Span::raw("Health | "),
+            Span::styled(filled, Style::default().bold().green()),
+            Span::styled(missing, Style::default().bold().fg(Color::DarkGray)),
+            Span::raw("  "),
             Span::styled(
-                data.player_data.get_health().to_string(),
+                data.player_data.get_current_health().to_string(),
                 Style::default().bold().green(),
             ),
+            Span::styled("/", Style::default().fg(Color::DarkGray)),
+            Span::raw(data.player_data.get_max_health().to_string()),
+            Span::styled(" HP", Style::default().fg(Color::DarkGray)),
         ]);
         let damage = Line::from(vec![
             Span::raw("Damage | "),
@@ -72,16 +176,23 @@ impl Scene for StatisticsScene {
         ]);
         let (filled, missing) =
             calculate_bar(data.player_data.get_xp(), data.player_data.get_nxp(), 10);
-        let xpbar = Line::from(vec![
-            Span::raw("       | "),
-            Span::styled(filled, Style::default().bold().fg(Color::Gray)),
-            Span::styled(missing, Style::default().bold().fg(Color::DarkGray)),
-            Span::raw("  "),
-            Span::raw(data.player_data.get_xp().to_string()),
-            Span::styled("/", Style::default().fg(Color::DarkGray)),
-            Span::raw(data.player_data.get_nxp().to_string()),
-            Span::styled(" XP", Style::default().fg(Color::DarkGray)),
-        ]);
+        let xpbar = if data.player_data.is_max_level() {
+            Line::from(vec![
+                Span::raw("       | "),
+                Span::styled("MAX LEVEL", Style::default().bold().fg(Color::Yellow)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("       | "),
+                Span::styled(filled, Style::default().bold().fg(Color::Gray)),
+                Span::styled(missing, Style::default().bold().fg(Color::DarkGray)),
+                Span::raw("  "),
+                Span::raw(data.player_data.get_xp().to_string()),
+                Span::styled("/", Style::default().fg(Color::DarkGray)),
+                Span::raw(data.player_data.get_nxp().to_string()),
+                Span::styled(" XP", Style::default().fg(Color::DarkGray)),
+            ])
+        };
         let coins = Line::from(vec![
             Span::raw("Coins  | "),
             Span::styled(
@@ -89,6 +200,17 @@ impl Scene for StatisticsScene {
                 Style::default().bold().fg(Color::LightYellow),
             ),
         ]);
+        let stat_points = Line::from(vec![
+            Span::raw("Points | "),
+            Span::styled(
+                data.player_data.get_stat_points().to_string(),
+                Style::default().bold().fg(Color::LightCyan),
+            ),
+            Span::styled(
+                " to spend in Attributes",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
 
         let mut buttons_spans: Vec<Span<'_>> = vec![];
         for text_id in 0..self.texts.len() {
@@ -104,32 +226,80 @@ impl Scene for StatisticsScene {
         let buttons = Line::from(buttons_spans);
 
         let equipment = Line::from("       | Equipment");
-        let sword = match &data.player_data.get_equipment().sword {
-            Some(item) => item.name.clone(),
-            None => "".into(),
-        };
-        let equipment_sword = Line::from(format!("Sword  | {}", sword));
-        let shield = match &data.player_data.get_equipment().shield {
-            Some(item) => item.name.clone(),
-            None => "".into(),
-        };
-        let equipment_shield = Line::from(format!("Shield | {}", shield));
+        let equipment_sword = Line::from(
+            [Span::raw("Sword  | ")]
+                .into_iter()
+                .chain(item_spans(&data.player_data.get_equipment().sword))
+                .collect::<Vec<_>>(),
+        );
+        let equipment_shield = Line::from(
+            [Span::raw("Shield | ")]
+                .into_iter()
+                .chain(item_spans(&data.player_data.get_equipment().shield))
+                .collect::<Vec<_>>(),
+        );
+        let mut set_bonuses = vec![];
+        for (set, bonus) in data.player_data.get_equipment().active_set_bonuses() {
+            set_bonuses.push(Line::from(vec![
+                Span::raw("Set    | "),
+                Span::styled(
+                    format!("{} ({} pieces)", set.name, bonus.pieces),
+                    Style::default().bold().fg(Color::LightMagenta),
+                ),
+                Span::styled(
+                    format!(
+                        " +{} HP, +{} damage, +{} defence",
+                        bonus.health, bonus.damage, bonus.defence
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        let mut skills = vec![empty.clone(), Line::from("       | Skills")];
+        for skill in data.player_data.get_class().skills() {
+            let unlocked = skill.unlock_level <= data.player_data.get_level();
+            let name_style = if unlocked {
+                Style::default().bold()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let info = if unlocked {
+                format!(" - {}", skill.description)
+            } else {
+                format!(" - {} (level {})", skill.description, skill.unlock_level)
+            };
+            skills.push(Line::from(vec![
+                Span::raw("       | "),
+                Span::styled(skill.name, name_style),
+                Span::styled(info, Style::default().fg(Color::DarkGray)),
+            ]));
+        }
 
-        let paragraph = Paragraph::new(vec![
+        let mut lines = vec![
             playername,
+            class,
+            mode,
             empty.clone(),
             health,
             damage,
             coins,
             level,
             xpbar,
+            stat_points,
             empty.clone(),
             buttons,
             empty.clone(),
-            equipment,
-            equipment_sword,
-            equipment_shield,
-        ]);
+        ];
+        match self.page {
+            Page::Equipment => {
+                lines.extend([equipment, equipment_sword, equipment_shield]);
+                lines.extend(set_bonuses);
+                lines.extend(skills);
+            }
+            Page::Lifetime => lines.extend(lifetime_lines(data)),
+        }
+        let paragraph = Paragraph::new(lines);
         let area = Rect {
             x: 0,
             y: 0,
@@ -144,15 +314,11 @@ impl Scene for StatisticsScene {
             return;
         }
         match key.code {
-            KeyCode::Right => {
-                if self.choosen_text_id + 1 < (self.texts.len() as i32) {
-                    self.choosen_text_id += 1;
-                }
+            KeyCode::Right if self.choosen_text_id + 1 < (self.texts.len() as i32) => {
+                self.choosen_text_id += 1;
             }
-            KeyCode::Left => {
-                if self.choosen_text_id > 0 {
-                    self.choosen_text_id -= 1;
-                }
+            KeyCode::Left if self.choosen_text_id > 0 => {
+                self.choosen_text_id -= 1;
             }
             KeyCode::Enter => match self.texts[self.choosen_text_id as usize] {
                 "Battle" => {
@@ -164,18 +330,34 @@ impl Scene for StatisticsScene {
                     // };
                     // data.scene_data_transfer = Some(json_battle);
                     // data.current_scene = BattleScene::scene_id()
-                    data.current_scene = NBattleScene::scene_id()
+                    data.current_scene = ArenaSelectScene::scene_id()
                 }
                 "Shop" => data.current_scene = ShopScene::scene_id(),
+                "Craft" => data.current_scene = CraftingScene::scene_id(),
+                "Inventory" => data.current_scene = InventoryScene::scene_id(),
+                "Attributes" => data.current_scene = AttributesScene::scene_id(),
+                "Skills" => data.current_scene = SkillTreeScene::scene_id(),
+                "Rest" => data.player_data.rest(),
                 "Change nickname" => data.current_scene = UsernameScene::scene_id(),
+                "Hall of fame" => data.current_scene = HallOfFameScene::scene_id(),
+                "Achievements" => data.current_scene = AchievementsScene::scene_id(),
+                "Statistics" => {
+                    self.page = if self.page == Page::Lifetime {
+                        Page::Equipment
+                    } else {
+                        Page::Lifetime
+                    }
+                }
                 "Save" => write_save(&data.player_data),
-                "Load" => {
-                    if let Some(saved_data) = load_save() {
+                "Load" => match load_save(data.player_data.get_save_slot()) {
+                    Ok(Some(saved_data)) => {
                         let msg_queue = data.player_data.get_message_queue();
                         data.player_data = saved_data;
                         data.player_data.set_message_queue(msg_queue);
                     }
-                }
+                    Ok(None) => (),
+                    Err(err) => self.message_queue.add_message(err),
+                },
                 "Exit" => data.terminate = true,
                 _ => (),
             },
@@ -1,18 +1,24 @@
 use super::{stats::StatisticsScene, Scene, SharedData};
-use crate::game::message_queue::MessageQueue;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use ratatui::{prelude::Rect, widgets::Paragraph};
+use crate::game::{
+    message_queue::MessageQueue,
+    player::{Player, MAX_NAME_LENGTH},
+    ui::text_input::TextInput,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{prelude::Rect, style::Style, text::Line, widgets::Paragraph};
 
 const SCENE_ID: i32 = 0;
 
 pub struct UsernameScene {
-    name: String,
+    name: TextInput,
     message_queue: MessageQueue,
 }
 impl UsernameScene {
-    pub fn new() -> Self {
+    pub fn new(data: &SharedData) -> Self {
         UsernameScene {
-            name: "".into(),
+            name: TextInput::new(MAX_NAME_LENGTH)
+                .with_charset(Player::is_name_grapheme)
+                .with_value(data.player_data.get_name()),
             message_queue: MessageQueue::default(),
         }
     }
@@ -32,35 +38,39 @@ impl Scene for UsernameScene {
     }
 
     fn handle_input(&mut self, key: KeyEvent, data: &mut SharedData) {
-        if key.kind != KeyEventKind::Press {
+        if key.kind != KeyEventKind::Press || self.name.handle_key(&key) {
             return;
         }
-        if let KeyCode::Char(nkey) = key.code {
-            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                let nkey = (nkey as u8) - 32;
-                self.name.push(nkey as char);
-            } else {
-                self.name.push(nkey);
-            }
-        }
-        if key.code == KeyCode::Backspace {
-            self.name.pop();
-        }
-        if key.code == KeyCode::Enter {
-            data.player_data.set_name(self.name.clone());
-            data.current_scene = StatisticsScene::scene_id();
+        match key.code {
+            KeyCode::Enter => match Player::validate_name(self.name.value()) {
+                Ok(()) => {
+                    data.player_data.set_name(self.name.value().trim().into());
+                    data.current_scene = StatisticsScene::scene_id();
+                }
+                Err(err) => self.message_queue.add_message(err.into()),
+            },
+            KeyCode::Esc => data.current_scene = StatisticsScene::scene_id(),
+            _ => (),
         }
     }
 
+    fn handle_paste(&mut self, text: &str, _: &mut SharedData) {
+        self.name.insert_str(text);
+    }
+
     fn render(&self, frame: &mut crate::Frame, _: &SharedData) {
-        let p = Paragraph::new(format!("Enter your new nickname:\n{}", self.name));
+        let width = frame.size().width as usize - 1;
+        let lines = vec![
+            Line::from("Enter your new nickname:"),
+            self.name.line(width, Style::default()),
+        ];
         let area = Rect {
             x: 0,
             y: 0,
             width: frame.size().width - 1,
             height: 4,
         };
-        frame.render_widget(p, area)
+        frame.render_widget(Paragraph::new(lines), area)
     }
 
     fn update(&mut self, _: &mut SharedData) {}
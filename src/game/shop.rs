@@ -0,0 +1,33 @@
+use super::item::{ConsumableEffect, Item, ItemProperties, ItemType};
+
+/// Everything the shop sells, in the order it is listed.
+pub fn shop_items() -> Vec<Item> {
+    let sword = Item::new(
+        "Sample Sword",
+        ItemType::Sword,
+        10,
+        ItemProperties {
+            damage: 10,
+            defence: 0,
+        },
+    );
+    let shield = Item::new(
+        "Sample Shield",
+        ItemType::Shield,
+        10,
+        ItemProperties {
+            damage: 0,
+            defence: 4,
+        },
+    );
+    let healing_potion = Item::consumable("Healing Potion", 5, ConsumableEffect::Heal(50));
+    let strength_potion = Item::consumable("Strength Potion", 8, ConsumableEffect::Buff(5));
+    let escape_scroll = Item::consumable("Escape Scroll", 12, ConsumableEffect::Escape);
+    vec![
+        sword,
+        shield,
+        healing_potion,
+        strength_potion,
+        escape_scroll,
+    ]
+}
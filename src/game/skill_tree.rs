@@ -0,0 +1,127 @@
+use super::class::SkillEffect;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ActiveSkill {
+    /// Attack with doubled damage.
+    PowerStrike,
+    /// Restore a third of maximum health.
+    SecondWind,
+}
+
+pub enum SkillKind {
+    Passive(SkillEffect),
+    /// Can be used once per battle.
+    Active(ActiveSkill),
+}
+
+pub struct SkillNode {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub cost: u128,
+    pub tier: usize,
+    pub prerequisites: &'static [&'static str],
+    pub kind: SkillKind,
+}
+
+pub const SKILL_TREE: &[SkillNode] = &[
+    SkillNode {
+        id: "toughness",
+        name: "Toughness",
+        description: "+30 health",
+        cost: 1,
+        tier: 0,
+        prerequisites: &[],
+        kind: SkillKind::Passive(SkillEffect::BonusHealth(30)),
+    },
+    SkillNode {
+        id: "sharpness",
+        name: "Sharpness",
+        description: "+3 damage",
+        cost: 1,
+        tier: 0,
+        prerequisites: &[],
+        kind: SkillKind::Passive(SkillEffect::BonusDamage(3)),
+    },
+    SkillNode {
+        id: "iron_skin",
+        name: "Iron Skin",
+        description: "+3 defence",
+        cost: 2,
+        tier: 1,
+        prerequisites: &["toughness"],
+        kind: SkillKind::Passive(SkillEffect::BonusDefence(3)),
+    },
+    SkillNode {
+        id: "power_strike",
+        name: "Power Strike",
+        description: "Active: attack with doubled damage once per battle",
+        cost: 2,
+        tier: 1,
+        prerequisites: &["sharpness"],
+        kind: SkillKind::Active(ActiveSkill::PowerStrike),
+    },
+    SkillNode {
+        id: "keen_eye",
+        name: "Keen Eye",
+        description: "+10% critical hit chance",
+        cost: 2,
+        tier: 1,
+        prerequisites: &["sharpness"],
+        kind: SkillKind::Passive(SkillEffect::CritChance(10)),
+    },
+    SkillNode {
+        id: "second_wind",
+        name: "Second Wind",
+        description: "Active: restore a third of your health once per battle",
+        cost: 3,
+        tier: 2,
+        prerequisites: &["iron_skin"],
+        kind: SkillKind::Active(ActiveSkill::SecondWind),
+    },
+    SkillNode {
+        id: "fleet_foot",
+        name: "Fleet Foot",
+        description: "+10% dodge chance",
+        cost: 2,
+        tier: 2,
+        prerequisites: &["keen_eye"],
+        kind: SkillKind::Passive(SkillEffect::DodgeChance(10)),
+    },
+    SkillNode {
+        id: "berserker",
+        name: "Berserker",
+        description: "+8 damage",
+        cost: 4,
+        tier: 3,
+        prerequisites: &["power_strike", "second_wind"],
+        kind: SkillKind::Passive(SkillEffect::BonusDamage(8)),
+    },
+];
+
+impl ActiveSkill {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActiveSkill::PowerStrike => "Power Strike",
+            ActiveSkill::SecondWind => "Second Wind",
+        }
+    }
+}
+
+pub fn find_node(id: &str) -> Option<&'static SkillNode> {
+    SKILL_TREE.iter().find(|node| node.id == id)
+}
+
+/// Nodes of a single tier, in the order they are declared.
+pub fn tier_nodes(tier: usize) -> Vec<&'static SkillNode> {
+    SKILL_TREE.iter().filter(|node| node.tier == tier).collect()
+}
+
+pub fn tiers_count() -> usize {
+    SKILL_TREE
+        .iter()
+        .map(|node| node.tier + 1)
+        .max()
+        .unwrap_or(0)
+}
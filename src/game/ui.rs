@@ -1,5 +1,7 @@
+pub mod text_input;
+
 use crossterm::{
-    event::PopKeyboardEnhancementFlags,
+    event::{DisableBracketedPaste, PopKeyboardEnhancementFlags},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -23,6 +25,10 @@ pub fn setup_terminal() -> Result<Terminal> {
 
 pub fn restore_terminal(mut terminal: Terminal) -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     Ok(())
 }
@@ -0,0 +1,290 @@
+//! Single line text input shared by every prompt in the game. The value is
+//! edited by grapheme clusters, so accented letters, emoji and CJK text move
+//! and delete as one character and take up their real width on screen.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+pub struct TextInput {
+    value: String,
+    /// Cursor position in graphemes, `0..=len`.
+    cursor: usize,
+    max_length: usize,
+    allowed: fn(&str) -> bool,
+}
+
+impl TextInput {
+    pub fn new(max_length: usize) -> Self {
+        TextInput {
+            value: "".into(),
+            cursor: 0,
+            max_length,
+            allowed: |_| true,
+        }
+    }
+
+    /// Restricts which graphemes can be typed or pasted. A combining mark is
+    /// checked together with the character it lands on.
+    pub fn with_charset(mut self, allowed: fn(&str) -> bool) -> Self {
+        self.allowed = allowed;
+        self
+    }
+
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.value.clear();
+        self.cursor = 0;
+        self.insert_str(value);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Length in graphemes, which is what `max_length` limits.
+    pub fn len(&self) -> usize {
+        self.value.graphemes(true).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Byte offset of the grapheme at `index`, or the end of the value.
+    fn byte_offset(&self, index: usize) -> usize {
+        self.value
+            .grapheme_indices(true)
+            .nth(index)
+            .map_or(self.value.len(), |(offset, _)| offset)
+    }
+
+    fn accepts(&self, grapheme: &str) -> bool {
+        !grapheme.chars().any(char::is_control) && (self.allowed)(grapheme)
+    }
+
+    /// Inserts text at the cursor, skipping graphemes outside the charset and
+    /// stopping once the maximum length is reached.
+    pub fn insert_str(&mut self, text: &str) {
+        for grapheme in text.graphemes(true) {
+            let offset = self.byte_offset(self.cursor);
+            let mut value = self.value.clone();
+            value.insert_str(offset, grapheme);
+            let length = value.graphemes(true).count();
+            if length > self.max_length {
+                break;
+            }
+            // A combining mark merges into the previous grapheme instead of
+            // adding a new one, so the cursor only moves if the count grew.
+            let grown = length - self.len();
+            let inserted = (self.cursor + grown).saturating_sub(1);
+            if !value
+                .graphemes(true)
+                .nth(inserted)
+                .is_some_and(|grapheme| self.accepts(grapheme))
+            {
+                continue;
+            }
+            self.value = value;
+            self.cursor += grown;
+        }
+    }
+
+    fn remove_range(&mut self, from: usize, to: usize) {
+        let start = self.byte_offset(from);
+        let end = self.byte_offset(to);
+        self.value.replace_range(start..end, "");
+        self.cursor = from;
+    }
+
+    /// Start of the word before the cursor, skipping whitespace first.
+    fn previous_word(&self) -> usize {
+        let graphemes: Vec<&str> = self.value.graphemes(true).collect();
+        let mut index = self.cursor;
+        while index > 0 && graphemes[index - 1].trim().is_empty() {
+            index -= 1;
+        }
+        while index > 0 && !graphemes[index - 1].trim().is_empty() {
+            index -= 1;
+        }
+        index
+    }
+
+    /// End of the word after the cursor, skipping whitespace first.
+    fn next_word(&self) -> usize {
+        let graphemes: Vec<&str> = self.value.graphemes(true).collect();
+        let mut index = self.cursor;
+        while index < graphemes.len() && graphemes[index].trim().is_empty() {
+            index += 1;
+        }
+        while index < graphemes.len() && !graphemes[index].trim().is_empty() {
+            index += 1;
+        }
+        index
+    }
+
+    /// Applies an editing key. Returns `false` for keys the input doesn't use,
+    /// so the scene can handle them itself (Enter, Esc, arrows up and down).
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let control = key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.remove_range(self.previous_word(), self.cursor)
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.remove_range(0, self.cursor)
+            }
+            KeyCode::Char(ch) if !control => {
+                // Terminals that don't report the shifted key send the base
+                // character along with the Shift modifier.
+                if key.modifiers.contains(KeyModifiers::SHIFT) && ch.is_lowercase() {
+                    self.insert_str(&ch.to_uppercase().to_string());
+                } else {
+                    self.insert_str(&ch.to_string());
+                }
+            }
+            KeyCode::Backspace if control => self.remove_range(self.previous_word(), self.cursor),
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.remove_range(self.cursor - 1, self.cursor)
+            }
+            KeyCode::Delete if control => {
+                let end = self.next_word();
+                let cursor = self.cursor;
+                self.remove_range(cursor, end)
+            }
+            KeyCode::Delete if self.cursor < self.len() => {
+                let cursor = self.cursor;
+                self.remove_range(cursor, cursor + 1)
+            }
+            KeyCode::Left if control => self.cursor = self.previous_word(),
+            KeyCode::Right if control => self.cursor = self.next_word(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            KeyCode::Backspace | KeyCode::Delete => (),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Renders the value with a block cursor, scrolled so the cursor stays
+    /// visible within `width` terminal cells.
+    pub fn line(&self, width: usize, style: Style) -> Line<'static> {
+        let graphemes: Vec<&str> = self.value.graphemes(true).collect();
+        let cursor_grapheme = graphemes.get(self.cursor).copied().unwrap_or(" ");
+        let cursor_width = cursor_grapheme.width().max(1);
+        let width = width.max(cursor_width);
+
+        let mut start = self.cursor;
+        let mut used = cursor_width;
+        while start > 0 && used + graphemes[start - 1].width() <= width {
+            start -= 1;
+            used += graphemes[start].width();
+        }
+        let mut end = (self.cursor + 1).min(graphemes.len());
+        while end < graphemes.len() && used + graphemes[end].width() <= width {
+            used += graphemes[end].width();
+            end += 1;
+        }
+
+        let before = graphemes[start..self.cursor].concat();
+        let after = if self.cursor < end {
+            graphemes[self.cursor + 1..end].concat()
+        } else {
+            "".into()
+        };
+        Line::from(vec![
+            Span::styled(before, style),
+            Span::styled(
+                cursor_grapheme.to_string(),
+                style.add_modifier(Modifier::REVERSED),
+            ),
+            Span::styled(after, style),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) {
+        input.handle_key(&KeyEvent::new(code, modifiers));
+    }
+
+    fn type_str(input: &mut TextInput, text: &str) {
+        for ch in text.chars() {
+            press(input, KeyCode::Char(ch), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn shift_uppercases_non_ascii_letters() {
+        let mut input = TextInput::new(16);
+        press(&mut input, KeyCode::Char('ä'), KeyModifiers::SHIFT);
+        press(&mut input, KeyCode::Char('ж'), KeyModifiers::SHIFT);
+        press(&mut input, KeyCode::Char('!'), KeyModifiers::SHIFT);
+        assert_eq!(input.value(), "ÄЖ!");
+    }
+
+    #[test]
+    fn edits_by_grapheme() {
+        let mut input = TextInput::new(16).with_value("ae\u{301}🙂");
+        assert_eq!(input.len(), 3);
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(input.value(), "a🙂");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(input.value(), "🙂");
+    }
+
+    #[test]
+    fn respects_max_length_and_charset() {
+        let mut input = TextInput::new(4)
+            .with_charset(|grapheme| grapheme.chars().all(|ch| ch.is_ascii_digit()));
+        input.insert_str("12a3\n456");
+        assert_eq!(input.value(), "1234");
+        type_str(&mut input, "7");
+        assert_eq!(input.value(), "1234");
+    }
+
+    #[test]
+    fn charset_checks_marks_with_their_base() {
+        let mut input = TextInput::new(16)
+            .with_charset(|grapheme| grapheme.chars().all(|ch| ch.is_ascii_digit()));
+        type_str(&mut input, "1\u{301}2");
+        assert_eq!(input.value(), "12");
+    }
+
+    #[test]
+    fn deletes_words() {
+        let mut input = TextInput::new(32).with_value("Sir Lancelot  the Brave");
+        press(&mut input, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "Sir Lancelot  the ");
+        press(&mut input, KeyCode::Backspace, KeyModifiers::CONTROL);
+        assert_eq!(input.value(), "Sir Lancelot  ");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Delete, KeyModifiers::CONTROL);
+        assert_eq!(input.value(), " Lancelot  ");
+    }
+
+    #[test]
+    fn line_keeps_cursor_visible() {
+        let input = TextInput::new(32).with_value("abcdefghij");
+        let line = input.line(4, Style::default());
+        let text: String = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "hij ");
+    }
+}
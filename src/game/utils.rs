@@ -12,6 +12,16 @@ use std::{
 };
 
 const SAVE_PATH: &str = "/YAPPY";
+pub const SAVE_SLOTS: u8 = 3;
+
+/// The first slot keeps the old file name so existing saves still load.
+fn save_file_name(slot: u8) -> String {
+    if slot <= 1 {
+        "save.data".into()
+    } else {
+        format!("save{}.data", slot)
+    }
+}
 
 fn linux_save(player: &Player) {
     if let Some(home_dir) = env::var_os("HOME") {
@@ -22,7 +32,7 @@ fn linux_save(player: &Player) {
             let _ = fs::create_dir_all(&save_dir);
         }
 
-        let full_file_path = &format!("{}/save.data", save_dir);
+        let full_file_path = &format!("{}/{}", save_dir, save_file_name(player.get_save_slot()));
         let mut file = File::create(full_file_path)
             .unwrap_or_else(|_| panic!("Failed to open the file: {}", full_file_path));
 
@@ -42,39 +52,103 @@ pub fn write_save(player: &Player) {
     }
 }
 
-fn linux_load() -> Option<Player> {
+/// `Ok(None)` when the slot is empty, `Err` when the file can't be read.
+fn linux_load(slot: u8) -> Result<Option<Player>, String> {
     if let Some(home_dir) = env::var_os("HOME") {
         let save_dir = format!("{}{}", home_dir.to_string_lossy(), SAVE_PATH);
 
-        let full_file_path = &format!("{}/save.data", save_dir);
-        let mut file = File::open(full_file_path).unwrap();
+        let full_file_path = &format!("{}/{}", save_dir, save_file_name(slot));
+        // Hardcore deaths delete the save, so a missing file is expected
+        let Ok(mut file) = File::open(full_file_path) else {
+            return Ok(None);
+        };
 
         let mut file_content: String = "".into();
         let _ = file.read_to_string(&mut file_content);
 
         let parsed: Result<Player, serde_json::Error> = serde_json::from_str(&file_content);
-        match parsed {
-            Ok(plr) => return Some(plr),
-            Err(err) => panic!("Corrupted save file. {}", err),
-        }
+        return match parsed {
+            Ok(mut plr) => {
+                plr.set_save_slot(slot);
+                plr.sanitize();
+                Ok(Some(plr))
+            }
+            Err(err) => Err(format!("Corrupted save file. {}", err)),
+        };
     }
-    None
+    Ok(None)
 }
 
-fn windows_load() -> Option<Player> {
+fn windows_load(_: u8) -> Result<Option<Player>, String> {
     todo!()
 }
 
-pub fn load_save() -> Option<Player> {
+pub fn load_save(slot: u8) -> Result<Option<Player>, String> {
     match std::env::consts::OS {
-        "linux" => linux_load(),
-        "windows" => windows_load(),
+        "linux" => linux_load(slot),
+        "windows" => windows_load(slot),
         _ => panic!("can't work with your OS lol get better + ratio"),
     }
 }
 
+/// Removes the save file of a slot, used when a hardcore character dies.
+pub fn delete_save(slot: u8) {
+    if let Some(home_dir) = env::var_os("HOME") {
+        let save_dir = format!("{}{}", home_dir.to_string_lossy(), SAVE_PATH);
+        let _ = fs::remove_file(format!("{}/{}", save_dir, save_file_name(slot)));
+    }
+}
+
+/// Writes a data file next to the save, creating the save directory if needed.
+pub fn write_data_file(name: &str, content: &str) {
+    if let Some(home_dir) = env::var_os("HOME") {
+        let save_dir = format!("{}{}", home_dir.to_string_lossy(), SAVE_PATH);
+        if !Path::new(&save_dir).exists() {
+            let _ = fs::create_dir_all(&save_dir);
+        }
+        let _ = fs::write(format!("{}/{}", save_dir, name), content);
+    }
+}
+
+/// Reads a file placed next to the save, if there is one.
+pub fn read_data_file(name: &str) -> Option<String> {
+    let home_dir = env::var_os("HOME")?;
+    let path = format!("{}{}/{}", home_dir.to_string_lossy(), SAVE_PATH, name);
+    fs::read_to_string(path).ok()
+}
+
+/// Every file in a data directory as `(file name, content)`, sorted by name.
+pub fn read_data_dir(name: &str) -> Vec<(String, String)> {
+    let Some(home_dir) = env::var_os("HOME") else {
+        return vec![];
+    };
+    let path = format!("{}{}/{}", home_dir.to_string_lossy(), SAVE_PATH, name);
+    let Ok(entries) = fs::read_dir(path) else {
+        return vec![];
+    };
+    let mut files: Vec<(String, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            Some((entry.file_name().to_string_lossy().into_owned(), content))
+        })
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
 pub fn calculate_bar(value: u128, max_value: u128, precision: u32) -> (String, String) {
-    let percentage: u128 = value * <u32 as Into<u128>>::into(precision) / max_value;
+    let precision_u128: u128 = precision.into();
+    // A reached (or empty) maximum is a full bar, huge values are scaled down
+    // before multiplying so nothing overflows.
+    let percentage: u128 = if value >= max_value {
+        precision_u128
+    } else {
+        match value.checked_mul(precision_u128) {
+            Some(scaled) => scaled / max_value,
+            None => (value / (max_value / precision_u128)).min(precision_u128),
+        }
+    };
     let mut filled = String::from("");
     let mut empty = String::from("");
     for i in 0..precision {
@@ -91,7 +165,7 @@ pub fn render_border_type(paragraph: &Paragraph, title: &str, frame: &mut Frame,
     let block = Block::new()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .title(Title::from(title.clone()).alignment(Alignment::Right));
+        .title(Title::from(title).alignment(Alignment::Right));
     frame.render_widget(paragraph.clone().block(block), area);
 }
 
@@ -103,3 +177,24 @@ pub fn get_full_size_rect(frame: &Frame) -> Rect {
         height: frame.size().height,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn bar_always_has_full_precision(value: u128, max_value: u128, precision in 0u32..100) {
+            let (filled, empty) = calculate_bar(value, max_value, precision);
+            prop_assert_eq!(filled.chars().count() + empty.chars().count(), precision as usize);
+        }
+
+        #[test]
+        fn bar_is_full_when_value_reaches_max(max_value: u128, extra: u128) {
+            let (filled, empty) = calculate_bar(max_value.saturating_add(extra), max_value, 10);
+            prop_assert_eq!(filled.chars().count(), 10);
+            prop_assert!(empty.is_empty());
+        }
+    }
+}
@@ -2,13 +2,14 @@ mod game;
 use std::error::Error;
 use std::io::{stdout, Stdout};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::Instant;
 
 use crossterm::event::{
-    self, Event, KeyEventKind, KeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    self, EnableBracketedPaste, Event, KeyEventKind, KeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
 };
 use crossterm::execute;
-use game::scenes::stats::StatisticsScene;
+use game::scenes::new_game::NewGameScene;
 use game::scenes::{Scene, SceneManager};
 use game::ui::{restore_terminal, setup_terminal};
 use game::{player::Player, scenes::SharedData};
@@ -25,9 +26,11 @@ fn run(terminal: &mut Terminal) -> Result<()> {
         PushKeyboardEnhancementFlags(
             KeyboardEnhancementFlags::REPORT_EVENT_TYPES
                 .union(KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES)
+                .union(KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS)
         ),
+        EnableBracketedPaste,
     )?;
-    let starting_scene = StatisticsScene::new();
+    let starting_scene = NewGameScene::new();
     let scene_id = starting_scene.scene_id();
     let mut scene_manager = SceneManager::new(starting_scene);
 
@@ -35,20 +38,31 @@ fn run(terminal: &mut Terminal) -> Result<()> {
     player.set_message_queue(scene_manager.get_message_queue());
     let shared_data = Arc::new(Mutex::new(SharedData::new(player, scene_id)));
 
+    let mut last_tick = Instant::now();
     loop {
-        if event::poll(Duration::from_millis(1000 / 60))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Repeat {
-                    scene_manager.handle_input(key, &mut shared_data.lock().unwrap());
+        if event::poll(scene_manager.time_until_tick())? {
+            match event::read()? {
+                Event::Key(key) => {
+                    if key.kind != KeyEventKind::Repeat {
+                        scene_manager.handle_input(key, &mut shared_data.lock().unwrap());
+                    }
+                    let data: &SharedData = &shared_data.lock().unwrap();
+                    if data.is_terminating() {
+                        return Ok(());
+                    }
                 }
-                let data: &SharedData = &shared_data.lock().unwrap();
-                if data.is_terminating() {
-                    return Ok(());
+                Event::Paste(text) => {
+                    scene_manager.handle_paste(&text, &mut shared_data.lock().unwrap())
                 }
+                _ => (),
             }
         }
-        scene_manager.update(&mut shared_data.lock().unwrap());
-        let _ = terminal.draw(|f| scene_manager.render(f, &shared_data.lock().unwrap()));
+        let elapsed = last_tick.elapsed();
+        last_tick = Instant::now();
+        let mut data = shared_data.lock().unwrap();
+        data.add_play_time(elapsed);
+        scene_manager.advance(elapsed, &mut data);
+        let _ = terminal.draw(|f| scene_manager.render(f, &data));
     }
 }
 